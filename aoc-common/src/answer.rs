@@ -0,0 +1,138 @@
+//! What a puzzle part answers with.
+//!
+//! Answers keep their numeric value, so they can be compared, summed or range-checked, whatever
+//! integer type the solution happened to compute them in. Two answers are equal when they would
+//! be typed into the site the same way: `Answer::from(42u8) == Answer::from(42i64)`.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Any integer that fits in an `i128`, which covers every primitive type but `u128`.
+    Int(i128),
+    /// A `u128` too big for [`Answer::Int`].
+    Big(u128),
+    /// For puzzles whose answer is a word or code rather than a number.
+    Text(String),
+}
+
+impl Answer {
+    /// The answer as a number, if it is one small enough.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(*n),
+            Answer::Big(_) | Answer::Text(_) => None,
+        }
+    }
+
+    pub fn is_number(&self) -> bool {
+        !matches!(self, Answer::Text(_))
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or(Answer::Big(n), Answer::Int)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Reads an answer back from stored output. Anything that isn't an integer is text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(match (s.parse::<i128>(), s.parse::<u128>()) {
+            (Ok(n), _) => Answer::Int(n),
+            (_, Ok(n)) => Answer::Big(n),
+            _ => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        *self == other.parse::<Answer>().unwrap()
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<Answer> for &str {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widths() {
+        assert_eq!(Answer::from(42u8), Answer::from(42i64));
+        assert_eq!(Answer::from(-3i32).as_i128(), Some(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::Big(u128::MAX));
+        assert_eq!(Answer::from(7u128), Answer::Int(7));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("55621\n".parse(), Ok(Answer::Int(55621)));
+        assert_eq!(u128::MAX.to_string().parse(), Ok(Answer::Big(u128::MAX)));
+        assert_eq!("LRLR".parse(), Ok(Answer::from("LRLR")));
+        for answer in [Answer::from(-12i64), Answer::from(u128::MAX), "abc".into()] {
+            assert_eq!(answer.to_string().parse(), Ok(answer));
+        }
+    }
+
+    #[test]
+    fn test_eq_str() {
+        assert_eq!(Answer::from(6440u32), "6440");
+        assert_eq!("6440", Answer::from(6440u64));
+        assert_ne!(Answer::from(6440u32), "6441");
+        assert!(!Answer::from("part 2").is_number());
+    }
+}
@@ -0,0 +1,182 @@
+//! Finding where an iterate-until-repeat simulation starts looping.
+//!
+//! [`brent`] and [`floyd`] only need states to be `Eq` and `Clone` and keep no history, at the
+//! cost of stepping through the sequence more than once. [`nth_hashed`] remembers every state
+//! instead, for simulations where a single step is expensive.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence `x0, f(x0), f(f(x0)), ...` starts repeating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step that is part of the loop.
+    pub start: usize,
+    /// How many steps it takes to come back round.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as step `n`'s.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+fn advance<S>(state: S, steps: usize, step: &mut impl FnMut(&S) -> S) -> S {
+    (0..steps).fold(state, |state, _| step(&state))
+}
+
+/// Finds the loop with Brent's algorithm, which usually needs fewer steps than Floyd's.
+///
+/// Never returns if the sequence doesn't repeat, which can't happen when there are only finitely
+/// many states.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Look for the loop length by teleporting the tortoise to the hare at each power of two
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a whole loop ahead, they first meet where the loop starts
+    let mut tortoise = initial.clone();
+    let mut hare = advance(initial, length, &mut step);
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Finds the loop with Floyd's tortoise and hare.
+///
+/// Never returns if the sequence doesn't repeat, which can't happen when there are only finitely
+/// many states.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut tortoise = initial;
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps, skipping the whole loops in between.
+pub fn nth<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = brent(initial.clone(), &mut step);
+    advance(initial, cycle.equivalent_step(n), &mut step)
+}
+
+/// Like [`nth`], but remembers every state so each step is only simulated once.
+pub fn nth_hashed<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    while history.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: history.len() - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(n));
+        }
+        seen.insert(state.clone(), history.len());
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(start: usize, length: usize) -> Cycle {
+        Cycle { start, length }
+    }
+
+    /// The loop found by remembering every state, to check the finders against.
+    fn by_history(initial: u64, step: impl Fn(&u64) -> u64) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut state = initial;
+        for i in 0.. {
+            if let Some(&start) = seen.get(&state) {
+                return cycle(start, i - start);
+            }
+            seen.insert(state, i);
+            state = step(&state);
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_finders_agree() {
+        for modulus in [7, 97, 255, 1000, 4096] {
+            for initial in [0, 1, 3, 42] {
+                let step = |&x: &u64| (x * x + 1) % modulus;
+                let expected = by_history(initial, step);
+                assert_eq!(brent(initial, step), expected, "x0 = {}", initial);
+                assert_eq!(floyd(initial, step), expected, "x0 = {}", initial);
+            }
+        }
+    }
+
+    #[test]
+    fn test_immediate_loop() {
+        assert_eq!(brent(5, |&x| x), cycle(0, 1));
+        assert_eq!(floyd(5, |&x| x), cycle(0, 1));
+        // 0 -> 1 -> 2 -> 0
+        let step = |&x: &u32| (x + 1) % 3;
+        assert_eq!(brent(0, step), cycle(0, 3));
+    }
+
+    #[test]
+    fn test_nth() {
+        // 10 -> 9 -> ... -> 5, then 4 -> 2 -> 3 -> 4 forever
+        let step = |&x: &u32| if x > 4 { x - 1 } else { 2 + (x - 1) % 3 };
+        let found = brent(10, step);
+        assert_eq!(found, cycle(6, 3));
+        assert_eq!(found.equivalent_step(4), 4);
+        assert_eq!(found.equivalent_step(1_000_000), 7);
+
+        for n in 0..40 {
+            let expected = (0..n).fold(10, |x, _| step(&x));
+            assert_eq!(nth(10, step, n), expected, "n = {}", n);
+            assert_eq!(nth_hashed(10, step, n), expected, "n = {}", n);
+        }
+        assert_eq!(nth(10, step, 1_000_000_000), nth(10, step, 7));
+        assert_eq!(nth_hashed(10, step, 1_000_000_000), nth(10, step, 7));
+    }
+}
@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Malformed puzzle input, pinned to where in the input it went wrong.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    /// Filled in by [`Solution::process`](crate::Solution::process) once the error reaches it.
+    pub day: Option<u8>,
+    /// 1-based line of the offending token.
+    pub line: usize,
+    /// 1-based column, in characters, of the offending token.
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+}
+
+impl AocError {
+    /// An error about `token`, which must be a slice of `input`.
+    pub fn at(input: &str, token: &str, message: impl Into<String>) -> Self {
+        let start = input.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+        assert!(
+            offset <= input.len(),
+            "token `{}` is not a slice of the input",
+            token
+        );
+        Self::at_offset(input, offset, token, message)
+    }
+
+    /// An error about `token`, found at byte `offset` into `input`.
+    pub fn at_offset(input: &str, offset: usize, token: &str, message: impl Into<String>) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        AocError {
+            day: None,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            token: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Moves an error found while parsing `part` on its own to where `part` sits in `input`.
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let start = AocError::at(input, part, "");
+        let column = if self.line == 1 {
+            start.column + self.column - 1
+        } else {
+            self.column
+        };
+        AocError {
+            line: start.line + self.line - 1,
+            column,
+            ..self
+        }
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day.get_or_insert(day);
+        self
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}: ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {} at `{}`",
+            self.line, self.column, self.message, self.token
+        )
+    }
+}
+
+impl Error for AocError {}
+
+/// Parses `token`, a slice of `input`, reporting where it was if it isn't a valid `T`.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, AocError> {
+    token
+        .parse()
+        .map_err(|_| AocError::at(input, token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "Game 1: 3 blue\nGame 2: 4 purple, 1 red";
+        let token = &input[input.find("purple").unwrap()..][..6];
+        let error = AocError::at(input, token, "unknown color").in_day(2);
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(
+            error.to_string(),
+            "day 2: line 2, column 11: unknown color at `purple`"
+        );
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let input = "ab\ncd";
+        let error = AocError::at(input, &input[input.len()..], "expected more input");
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.day, None);
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "#.\n\n..\n.x";
+        let part = &input[4..];
+        let error = AocError::at(part, &part[4..], "unknown tile").within(input, part);
+        assert_eq!((error.line, error.column), (4, 2));
+
+        let part = &input[1..];
+        let error = AocError::at(part, &part[..1], "unknown tile").within(input, part);
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+
+    #[test]
+    fn test_number() {
+        let input = "12 x4";
+        assert_eq!(number::<u32>(input, &input[..2]), Ok(12));
+        let error = number::<u32>(input, &input[3..]).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (1, 4, "x4")
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Finds the default input for `part` in a day directory.
+///
+/// Tries `inputN.txt`, then a shared `input.txt`, then `input1.txt` for days where both parts
+/// run on the same puzzle input. Empty placeholder files are skipped.
+pub fn default_path(day_dir: &Path, part: u8) -> io::Result<PathBuf> {
+    let candidates = [
+        format!("input{}.txt", part),
+        "input.txt".to_string(),
+        "input1.txt".to_string(),
+    ];
+    candidates
+        .iter()
+        .map(|name| day_dir.join(name))
+        .find(|path| fs::metadata(path).is_ok_and(|m| m.len() > 0))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no input file for part {} in {}", part, day_dir.display()),
+            )
+        })
+}
+
+/// Reads the puzzle input named by `arg`: a file path, `-` for stdin, or `None` for the
+/// default input of `part` in `day_dir`.
+pub fn read(arg: Option<&str>, day_dir: &Path, part: u8) -> io::Result<String> {
+    match arg {
+        Some("-") => read_stdin(),
+        Some(path) => read_path(Path::new(path)),
+        None => read_path(&default_path(day_dir, part)?),
+    }
+}
+
+/// Reads the input for a day binary from its first command line argument, exiting with a
+/// message if it can't be read.
+pub fn from_args(day_dir: &str, part: u8) -> String {
+    let arg = std::env::args().nth(1);
+    read(arg.as_deref(), Path::new(day_dir), part).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    })
+}
+
+pub fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(normalized(input))
+}
+
+fn read_path(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map(normalized)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// Evens out the differences between editors and platforms, so parsers only ever see one shape
+/// of input: no byte order mark, `\n` line endings, no whitespace at the end of a line and no
+/// blank lines at the end of the file. Blank lines between blocks are kept, and so is leading
+/// whitespace, which can matter.
+///
+/// Borrows the input when it is already in that shape, as generated inputs always are.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let body = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let clean = body.len() == input.len()
+        && !body.contains('\r')
+        && body.trim_end() == body
+        && body.lines().all(|line| line.trim_end() == line);
+    if clean {
+        return Cow::Borrowed(input);
+    }
+
+    // A lone `\r` is an old Mac line ending, so treat it like the rest
+    let unified = body.replace("\r\n", "\n").replace('\r', "\n");
+    let mut normalized = String::with_capacity(unified.len());
+    for line in unified.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    let end = normalized.trim_end().len();
+    normalized.truncate(end);
+    Cow::Owned(normalized)
+}
+
+/// The blocks of lines between blank lines, whatever the line endings, without their trailing
+/// newlines.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        match (line.trim().is_empty(), start) {
+            (false, None) => start = Some(offset),
+            (true, Some(from)) => {
+                blocks.push(input[from..offset].trim_end());
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+    if let Some(from) = start {
+        blocks.push(input[from..].trim_end());
+    }
+    blocks
+}
+
+fn normalized(input: String) -> String {
+    match normalize(&input) {
+        Cow::Borrowed(_) => input,
+        Cow::Owned(normalized) => normalized,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-common-{}-{}", name, process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_default_path_prefers_part_file() {
+        let dir = scratch_dir("prefers");
+        fs::write(dir.join("input1.txt"), "one").unwrap();
+        fs::write(dir.join("input2.txt"), "two").unwrap();
+        assert_eq!(default_path(&dir, 2).unwrap(), dir.join("input2.txt"));
+        assert_eq!(read(None, &dir, 1).unwrap(), "one");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_default_path_fallbacks() {
+        let dir = scratch_dir("fallbacks");
+        fs::write(dir.join("input1.txt"), "one").unwrap();
+        fs::write(dir.join("input2.txt"), "").unwrap();
+        assert_eq!(default_path(&dir, 2).unwrap(), dir.join("input1.txt"));
+
+        fs::write(dir.join("input.txt"), "shared").unwrap();
+        assert_eq!(default_path(&dir, 2).unwrap(), dir.join("input.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_normalize() {
+        let clean = "#.#\n\n  ..#";
+        assert!(matches!(normalize(clean), Cow::Borrowed(_)));
+        for saved in [
+            "#.#\r\n\r\n  ..#\r\n",
+            "\u{feff}#.#\n\n  ..#\n\n\n",
+            "#.#  \n \t\n  ..# \n",
+            "#.#\r\r  ..#\r",
+        ] {
+            assert_eq!(normalize(saved), clean, "{:?}", saved);
+        }
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(blocks("#.\n.#\n\n##\n"), vec!["#.\n.#", "##"]);
+        assert_eq!(blocks("#.\r\n.#\r\n\r\n\r\n##"), vec!["#.\r\n.#", "##"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_read_normalizes() {
+        let dir = scratch_dir("normalizes");
+        fs::write(dir.join("input1.txt"), "\u{feff}1,2\r\n3\r\n").unwrap();
+        assert_eq!(read(None, &dir, 1).unwrap(), "1,2\n3");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let dir = scratch_dir("missing");
+        assert!(default_path(&dir, 1).is_err());
+        let err = read(Some("does-not-exist.txt"), &dir, 1).unwrap_err();
+        assert!(err.to_string().contains("does-not-exist.txt"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,12 @@
+pub mod answer;
+pub mod cycle;
+pub mod error;
+pub mod input;
+pub mod parse;
+pub mod rng;
+pub mod solution;
+pub mod trace;
+
+pub use answer::Answer;
+pub use error::AocError;
+pub use solution::Solution;
@@ -0,0 +1,297 @@
+//! nom combinators for the shapes puzzle inputs keep coming in.
+//!
+//! Everything here fails with a [`ParseError`], which remembers where in the input it gave up and
+//! what it was looking for there. [`finish`] runs a parser over a whole input and turns that into
+//! an [`AocError`] with a line and column.
+
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{
+    alphanumeric1, char, digit1, line_ending, multispace0, none_of, space0, space1,
+};
+use nom::combinator::{all_consuming, consumed, cut, eof, map_opt, map_res, opt, peek, recognize};
+use nom::error::{context, ContextError, ErrorKind, FromExternalError};
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+
+use crate::AocError;
+
+/// Where a parser gave up, and what it expected to find there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<'a> {
+    /// The input that was left when the parser failed.
+    pub input: &'a str,
+    /// What should have come next, from the innermost [`context`] around the failure.
+    pub expected: Option<String>,
+    pub kind: ErrorKind,
+}
+
+pub type IResult<'a, T> = nom::IResult<&'a str, T, ParseError<'a>>;
+
+impl<'a> ParseError<'a> {
+    fn expecting(input: &'a str, expected: impl Into<String>) -> Self {
+        ParseError {
+            input,
+            expected: Some(expected.into()),
+            kind: ErrorKind::Verify,
+        }
+    }
+
+    /// Points the error at the next word of `input`, the whole text the parser was given.
+    pub fn into_aoc_error(self, input: &str) -> AocError {
+        let rest = self.input.trim_start_matches([' ', '\t']);
+        let token = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+        let message = match self.expected {
+            Some(expected) => format!("expected {}", expected),
+            None if self.kind == ErrorKind::Eof => "unexpected input".to_string(),
+            None => format!("unexpected input ({})", self.kind.description()),
+        };
+        AocError::at(input, token, message)
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for ParseError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        ParseError {
+            input,
+            expected: None,
+            kind,
+        }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<'a> ContextError<&'a str> for ParseError<'a> {
+    fn add_context(_input: &'a str, ctx: &'static str, mut other: Self) -> Self {
+        other.expected.get_or_insert_with(|| ctx.to_string());
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for ParseError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _e: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+/// Runs `parser` over all of `input`, allowing trailing whitespace.
+pub fn finish<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> Result<T, AocError> {
+    match all_consuming(terminated(parser, multispace0))(input) {
+        Ok((_, parsed)) => Ok(parsed),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => Err(error.into_aoc_error(input)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never ask for more input"),
+    }
+}
+
+/// Exactly `text`, reported as expecting `` `text` `` if it isn't there.
+pub fn symbol<'a>(text: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input| {
+        tag::<_, _, ParseError>(text)(input)
+            .map_err(|e| e.map(|_| ParseError::expecting(input, format!("`{}`", text))))
+    }
+}
+
+/// A whole number, optionally negative.
+pub fn number<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(char('-')), digit1)), str::parse),
+    )(input)
+}
+
+/// A run of letters and digits, like the node names in day 8.
+pub fn word(input: &str) -> IResult<'_, &str> {
+    context("a name", alphanumeric1)(input)
+}
+
+/// Numbers separated by spaces on a single line.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<'_, Vec<T>> {
+    terminated(separated_list1(space1, number), space0)(input)
+}
+
+/// `label:` followed by whatever `value` parses, as in `Time: 7 15 30`.
+pub fn labelled<'a, T>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    preceded(tuple((symbol(label), symbol(":"), space0)), value)
+}
+
+/// `key = (left, right)`.
+pub fn key_pair<'a, K, A, B>(
+    key: impl FnMut(&'a str) -> IResult<'a, K>,
+    left: impl FnMut(&'a str) -> IResult<'a, A>,
+    right: impl FnMut(&'a str) -> IResult<'a, B>,
+) -> impl FnMut(&'a str) -> IResult<'a, (K, (A, B))> {
+    separated_pair(
+        key,
+        symbol(" = "),
+        // Past the `=` this can only be a malformed pair, not some other kind of line
+        cut(delimited(
+            symbol("("),
+            separated_pair(left, symbol(", "), right),
+            symbol(")"),
+        )),
+    )
+}
+
+/// One or more `item`s separated by commas, with optional spaces after each comma.
+pub fn comma_list<'a, T>(
+    item: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(pair(char(','), space0), item)
+}
+
+/// One `line` per line.
+pub fn lines<'a, T>(
+    line: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(line_ending, line)
+}
+
+/// Blocks separated by a blank line.
+pub fn blocks<'a, T>(
+    block: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// A rectangle of characters, one row per line, with `tile` turning each into a cell.
+///
+/// `tile` returns `None` for characters that don't belong in the grid. Unknown characters and
+/// rows of a different width from the first stop the parse rather than ending the grid early.
+pub fn grid<'a, T>(
+    tile: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<Vec<T>>> {
+    move |input| {
+        let row = terminated(
+            many1(map_opt(none_of("\r\n"), &tile)),
+            cut(context("a known tile", peek(alt((line_ending, eof))))),
+        );
+        let (rest, rows) = lines(consumed(row))(input)?;
+        let width = rows[0].1.len();
+        if let Some((line, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            let expected = format!("a row {} wide", width);
+            return Err(nom::Err::Failure(ParseError::expecting(line, expected)));
+        }
+        Ok((rest, rows.into_iter().map(|(_, row)| row).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(error: AocError) -> (usize, usize, String, String) {
+        (error.line, error.column, error.token, error.message)
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(finish("7  15   -30 ", numbers::<i32>), Ok(vec![7, 15, -30]));
+
+        let error = finish("1 2 x3 4", numbers::<u32>).unwrap_err();
+        assert_eq!(
+            position(error),
+            (1, 5, "x3".to_string(), "unexpected input".to_string())
+        );
+
+        let error = finish(
+            "1 2\n-3",
+            separated_pair(numbers::<u32>, line_ending, number::<u32>),
+        )
+        .unwrap_err();
+        assert_eq!(
+            position(error),
+            (2, 1, "-3".to_string(), "expected a number".to_string())
+        );
+    }
+
+    #[test]
+    fn test_labelled() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+        let races = separated_pair(
+            labelled("Time", numbers::<u32>),
+            line_ending,
+            labelled("Distance", numbers::<u32>),
+        );
+        assert_eq!(
+            finish(input, races),
+            Ok((vec![7, 15, 30], vec![9, 40, 200]))
+        );
+
+        let error = finish("Tim: 7", labelled("Time", numbers::<u32>)).unwrap_err();
+        assert_eq!(
+            position(error),
+            (1, 1, "Tim:".to_string(), "expected `Time`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_key_pair() {
+        let input = "AAA = (BBB, CCC)\nBBB = (DDD, EEE)";
+        let network = finish(input, lines(key_pair(word, word, word))).unwrap();
+        assert_eq!(network[1], ("BBB", ("DDD", "EEE")));
+
+        let error = finish("AAA = (BBB CCC)", key_pair(word, word, word)).unwrap_err();
+        assert_eq!(
+            position(error),
+            (1, 12, "CCC)".to_string(), "expected `, `".to_string())
+        );
+    }
+
+    #[test]
+    fn test_comma_list() {
+        assert_eq!(
+            finish("1,2, 3", comma_list(number::<u8>)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(finish("rn=1,cm-", comma_list(word)).ok(), None);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1 2\n3\n\n4\n\n5 6\n";
+        assert_eq!(
+            finish(input, blocks(lines(numbers::<u8>))),
+            Ok(vec![
+                vec![vec![1, 2], vec![3]],
+                vec![vec![4]],
+                vec![vec![5, 6]]
+            ])
+        );
+        assert_eq!(finish("1\r\n\r\n2", blocks(number::<u8>)), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_grid() {
+        let tiles = |c| "#.".contains(c).then_some(c == '#');
+        assert_eq!(
+            finish("#.\n.#\n", grid(tiles)),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+
+        let error = finish("#.\n.x", grid(tiles)).unwrap_err();
+        assert_eq!(
+            position(error),
+            (2, 2, "x".to_string(), "expected a known tile".to_string())
+        );
+
+        let error = finish("##\n#", grid(tiles)).unwrap_err();
+        assert_eq!(
+            position(error),
+            (2, 1, "#".to_string(), "expected a row 2 wide".to_string())
+        );
+
+        let patterns = finish("#.\n.#\n\n##\n##", blocks(grid(tiles))).unwrap();
+        assert_eq!(patterns.len(), 2);
+    }
+}
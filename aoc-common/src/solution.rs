@@ -0,0 +1,36 @@
+use crate::{input, Answer, AocError};
+
+/// The [`Solution::VARIANT`] of a part's original implementation.
+pub const MAIN_VARIANT: &str = "main";
+
+/// One half of a day's puzzle, as seen by the runner.
+///
+/// Every `day-XX` crate exposes a `Part1` and a `Part2` type implementing this, so the runner
+/// can find, time and print each part without knowing anything about how it is solved.
+/// Solving is split into `parse`, which turns the raw input into a typed intermediate, and
+/// `solve`, which works only on that intermediate, so the two can be timed separately.
+/// Malformed input is rejected by `parse`; `solve` may assume it was given valid input.
+pub trait Solution {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+    /// Which half of the puzzle this solves, 1 or 2.
+    const PART: u8;
+    /// Tells alternative implementations of the same part apart, e.g. a rewrite for speed that
+    /// should still agree with the original.
+    const VARIANT: &'static str = MAIN_VARIANT;
+
+    /// The parsed puzzle input. May borrow from the raw input.
+    type Parsed<'a>;
+
+    /// Called by [`Solution::process`] with input that has been through [`input::normalize`].
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError>;
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer;
+
+    /// Parses and solves `input`, however the file it came from was saved.
+    fn process(input: &str) -> Result<Answer, AocError> {
+        let input = input::normalize(input);
+        let parsed = Self::parse(&input).map_err(|e| e.in_day(Self::DAY))?;
+        Ok(Self::solve(parsed))
+    }
+}
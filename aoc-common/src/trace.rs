@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::span;
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Set to a file path to write a Chrome trace-event JSON file of every span.
+pub const CHROME_TRACE_VAR: &str = "AOC_CHROME_TRACE";
+/// Set to anything to print a per-span timing table to stderr on exit.
+pub const SPAN_TIMINGS_VAR: &str = "AOC_SPAN_TIMINGS";
+
+/// Keeps the installed tracing subscriber alive. Hold it until the end of `main`; dropping it
+/// flushes the Chrome trace and prints the span timing table.
+#[must_use]
+#[derive(Default)]
+pub struct TraceGuard {
+    chrome: Option<tracing_chrome::FlushGuard>,
+    timings: Option<SpanTimings>,
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        if let Some(chrome) = self.chrome.take() {
+            chrome.flush();
+        }
+        if let Some(timings) = &self.timings {
+            eprint!("{}", timings.table());
+        }
+    }
+}
+
+/// Installs a tracing subscriber for whichever outputs were asked for.
+///
+/// With the `tracy` feature, every `#[tracing::instrument]` span streams to a Tracy client.
+/// [`CHROME_TRACE_VAR`] and [`SPAN_TIMINGS_VAR`] turn on a Chrome trace file and a timing
+/// table. When none of these are enabled no subscriber is installed, so spans cost nothing.
+pub fn init() -> TraceGuard {
+    let chrome_path = env::var_os(CHROME_TRACE_VAR);
+    let timings = env::var_os(SPAN_TIMINGS_VAR).map(|_| SpanTimings::default());
+    if chrome_path.is_none() && timings.is_none() && !cfg!(feature = "tracy") {
+        return TraceGuard::default();
+    }
+
+    let (chrome_layer, chrome) = chrome_path
+        .map(|path| {
+            tracing_chrome::ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(false)
+                .build()
+        })
+        .unzip();
+
+    let subscriber = tracing_subscriber::registry()
+        .with(chrome_layer)
+        .with(timings.clone());
+    #[cfg(feature = "tracy")]
+    let subscriber = subscriber.with(tracing_tracy::TracyLayer::new());
+
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("tracing subscriber already installed");
+
+    TraceGuard { chrome, timings }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SpanStat {
+    calls: u64,
+    total: Duration,
+    children: Duration,
+}
+
+impl SpanStat {
+    /// Time spent in the span itself, excluding time in child spans.
+    fn self_time(&self) -> Duration {
+        self.total.saturating_sub(self.children)
+    }
+}
+
+/// Per-entry bookkeeping stored in each span's extensions.
+struct Entered {
+    at: Instant,
+    children: Duration,
+}
+
+/// A layer that adds up how long is spent in each span, keyed by `target::name`.
+#[derive(Debug, Default, Clone)]
+pub struct SpanTimings {
+    stats: Arc<Mutex<HashMap<String, SpanStat>>>,
+}
+
+impl SpanTimings {
+    /// Renders the collected timings, slowest self time first.
+    pub fn table(&self) -> String {
+        let stats = self.stats.lock().unwrap();
+        let mut rows: Vec<_> = stats.iter().collect();
+        rows.sort_by(|a, b| b.1.self_time().cmp(&a.1.self_time()).then(a.0.cmp(b.0)));
+
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:<width$} {:>8} {:>12} {:>12} {:>12}",
+            "span", "calls", "total", "self", "mean"
+        );
+        for (name, stat) in rows {
+            let _ = writeln!(
+                out,
+                "{:<width$} {:>8} {:>12.2?} {:>12.2?} {:>12.2?}",
+                name,
+                stat.calls,
+                stat.total,
+                stat.self_time(),
+                stat.total / stat.calls.max(1) as u32,
+            );
+        }
+        out
+    }
+}
+
+impl<S> Layer<S> for SpanTimings
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().replace(Entered {
+                at: Instant::now(),
+                children: Duration::ZERO,
+            });
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let Some(entered) = span.extensions_mut().remove::<Entered>() else {
+            return;
+        };
+        let elapsed = entered.at.elapsed();
+
+        if let Some(parent) = span.parent() {
+            if let Some(parent_entered) = parent.extensions_mut().get_mut::<Entered>() {
+                parent_entered.children += elapsed;
+            }
+        }
+
+        let key = format!("{}::{}", span.metadata().target(), span.name());
+        let mut stats = self.stats.lock().unwrap();
+        let stat = stats.entry(key).or_default();
+        stat.calls += 1;
+        stat.total += elapsed;
+        stat.children += entered.children;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tracing::instrument]
+    fn leaf() {
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    #[tracing::instrument]
+    fn outer() {
+        leaf();
+        leaf();
+    }
+
+    #[test]
+    fn test_span_timings() {
+        let timings = SpanTimings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.clone());
+        tracing::subscriber::with_default(subscriber, outer);
+
+        let stats = timings.stats.lock().unwrap();
+        let outer = stats["aoc_common::trace::tests::outer"];
+        let leaf = stats["aoc_common::trace::tests::leaf"];
+        assert_eq!(outer.calls, 1);
+        assert_eq!(leaf.calls, 2);
+        assert!(leaf.total >= Duration::from_millis(4));
+        assert_eq!(outer.children, leaf.total);
+        assert!(outer.self_time() < leaf.total);
+    }
+
+    #[test]
+    fn test_table() {
+        let timings = SpanTimings::default();
+        let subscriber = tracing_subscriber::registry().with(timings.clone());
+        tracing::subscriber::with_default(subscriber, outer);
+
+        let table = timings.table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("span"));
+        // leaf does all the sleeping, so it has the most self time
+        assert!(lines[1].starts_with("aoc_common::trace::tests::leaf"));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod direction;
+pub mod point;
+pub mod polygon;
+
+pub use direction::{Direction, Direction8};
+pub use point::Point;
+pub use polygon::{boundary_points, interior_points, shoelace_area};
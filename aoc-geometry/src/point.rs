@@ -0,0 +1,175 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{Direction, Direction8};
+
+/// A position on an unbounded integer plane, with `y` growing downwards like a grid's rows.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    pub fn step_n(self, direction: Direction, n: i64) -> Self {
+        self + direction.delta() * n
+    }
+
+    /// Steps once in `direction`, if that stays inside a `width` by `height` area at the origin.
+    pub fn step_within(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        let next = self.step(direction);
+        next.in_bounds(width, height).then_some(next)
+    }
+
+    pub fn in_bounds(self, width: usize, height: usize) -> bool {
+        self.to_unsigned()
+            .is_some_and(|(x, y)| x < width && y < height)
+    }
+
+    /// This point as `(x, y)` grid coordinates, if neither is negative.
+    pub fn to_unsigned(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    /// The four orthogonal neighbours, clockwise from up.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// All eight neighbours, clockwise from up.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |d| self + d.delta())
+    }
+
+    /// Taxicab distance: steps needed moving only orthogonally.
+    pub fn manhattan(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Chessboard distance: steps needed when diagonal moves are allowed.
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as i64, y as i64)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let point = Point::new(2, 3);
+        assert_eq!(point.step(Direction::Up), Point::new(2, 2));
+        assert_eq!(point.step_n(Direction::Left, 5), Point::new(-3, 3));
+        assert_eq!(
+            point - point.step(Direction::Down),
+            -Direction::Down.delta()
+        );
+    }
+
+    #[test]
+    fn test_step_within() {
+        let corner = Point::ORIGIN;
+        assert_eq!(corner.step_within(Direction::Up, 3, 2), None);
+        assert_eq!(corner.step_within(Direction::Left, 3, 2), None);
+        assert_eq!(
+            corner.step_within(Direction::Down, 3, 2),
+            Some(Point::new(0, 1))
+        );
+        assert_eq!(Point::new(2, 1).step_within(Direction::Right, 3, 2), None);
+        // stepping in from outside is fine as long as we land inside
+        assert_eq!(
+            Point::new(-1, 0).step_within(Direction::Right, 3, 2),
+            Some(corner)
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let point = Point::new(1, 1);
+        assert_eq!(point.neighbours4().count(), 4);
+        assert!(point.neighbours8().all(|n| point.chebyshev(n) == 1));
+        assert_eq!(
+            point
+                .neighbours8()
+                .filter(|&n| point.manhattan(n) == 2)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(b.manhattan(a), 9);
+        assert_eq!(a.chebyshev(b), 5);
+    }
+}
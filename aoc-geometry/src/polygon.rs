@@ -0,0 +1,76 @@
+use aoc_math::gcd;
+
+use crate::Point;
+
+/// Twice the signed area of the polygon; positive when `y`-down vertices run clockwise.
+fn double_signed_area(vertices: &[Point]) -> i64 {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+/// Area enclosed by a simple polygon, by the shoelace formula.
+///
+/// `vertices` are the corners in order, either way round, without repeating the first at the end.
+/// Lattice polygons can have half-integer areas; those are rounded down.
+pub fn shoelace_area(vertices: &[Point]) -> i64 {
+    double_signed_area(vertices).abs() / 2
+}
+
+/// The number of lattice points on the polygon's edges, corners included.
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    let next = vertices.iter().cycle().skip(1);
+    vertices
+        .iter()
+        .zip(next)
+        .map(|(&a, &b)| {
+            let d = b - a;
+            gcd(d.x.unsigned_abs(), d.y.unsigned_abs()) as i64
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside the polygon, by Pick's theorem.
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_signed_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(i64, i64)]) -> Vec<Point> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_square() {
+        let square = points(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(shoelace_area(&square), 16);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        let reversed: Vec<Point> = square.into_iter().rev().collect();
+        assert_eq!(shoelace_area(&reversed), 16);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let triangle = points(&[(0, 0), (3, 1), (1, 2)]);
+        // area 2.5, rounded down
+        assert_eq!(shoelace_area(&triangle), 2);
+        assert_eq!(boundary_points(&triangle), 3);
+        assert_eq!(interior_points(&triangle), 2);
+    }
+
+    #[test]
+    fn test_l_shape() {
+        let shape = points(&[(0, 0), (2, 0), (2, 1), (1, 1), (1, 2), (0, 2)]);
+        assert_eq!(shoelace_area(&shape), 3);
+        assert_eq!(boundary_points(&shape), 8);
+        assert_eq!(interior_points(&shape), 0);
+    }
+}
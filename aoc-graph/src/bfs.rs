@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The fewest steps from `start` to every node reachable from it.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Every node reachable from `start`, including `start` itself.
+pub fn flood_fill<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashSet<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                stack.push(next);
+            }
+        }
+    }
+    seen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Open cells of a small maze, as (x, y).
+    fn maze_successors<'a>(
+        maze: &'a [&'static str],
+    ) -> impl Fn(&(usize, usize)) -> Vec<(usize, usize)> + 'a {
+        move |&(x, y)| {
+            [
+                (x + 1, y),
+                (x.wrapping_sub(1), y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ]
+            .into_iter()
+            .filter(|&(x, y)| {
+                maze.get(y)
+                    .and_then(|row| row.as_bytes().get(x))
+                    .is_some_and(|&c| c == b'.')
+            })
+            .collect()
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = ["..#.", "#...", "..#.", "#.#."];
+        let distances = bfs((0, 0), maze_successors(&maze));
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(1, 1)], 2);
+        assert_eq!(distances[&(3, 0)], 5);
+        assert_eq!(distances[&(3, 3)], 6);
+        assert_eq!(distances[&(1, 3)], 4);
+        assert_eq!(distances.len(), 11);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let maze = ["..#.", "###.", "..#.", ".##."];
+        assert_eq!(
+            flood_fill((0, 0), maze_successors(&maze)),
+            HashSet::from([(0, 0), (1, 0)])
+        );
+        assert_eq!(flood_fill((0, 2), maze_successors(&maze)).len(), 3);
+        assert_eq!(flood_fill((3, 0), maze_successors(&maze)).len(), 4);
+    }
+}
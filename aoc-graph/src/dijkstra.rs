@@ -0,0 +1,198 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Nodes seen so far, numbered in discovery order so the heap only has to hold indices.
+struct Visited<N, C> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    /// Best known cost to each node and the node it was reached from.
+    best: Vec<(C, Option<usize>)>,
+}
+
+impl<N: Eq + Hash + Clone, C: Ord + Copy> Visited<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Visited {
+            nodes: vec![start.clone()],
+            index: HashMap::from([(start, 0)]),
+            best: vec![(zero, None)],
+        }
+    }
+
+    /// Records reaching `node` for `cost` from `parent`, returning its index if that's an
+    /// improvement.
+    fn relax(&mut self, node: N, cost: C, parent: usize) -> Option<usize> {
+        match self.index.entry(node) {
+            Entry::Occupied(entry) => {
+                let i = *entry.get();
+                (cost < self.best[i].0).then(|| {
+                    self.best[i] = (cost, Some(parent));
+                    i
+                })
+            }
+            Entry::Vacant(entry) => {
+                let i = self.nodes.len();
+                self.nodes.push(entry.key().clone());
+                entry.insert(i);
+                self.best.push((cost, Some(parent)));
+                Some(i)
+            }
+        }
+    }
+
+    fn path_to(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(parent) = self.best[i].1 {
+            path.push(self.nodes[parent].clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// The cheapest path from `start` to a node where `success` holds, guided by `heuristic`.
+///
+/// `heuristic` must never overestimate the remaining cost. Returns the path, `start` and goal
+/// included, and its total cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start, zero);
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if cost > visited.best[i].0 {
+            // A cheaper way here was already expanded
+            continue;
+        }
+        if success(&visited.nodes[i]) {
+            return Some((visited.path_to(i), cost));
+        }
+        for (next, step) in successors(&visited.nodes[i]) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+            if let Some(j) = visited.relax(next, next_cost, i) {
+                heap.push(Reverse((estimate, next_cost, j)));
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest path from `start` to a node where `success` holds, and its cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    success: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), success)
+}
+
+/// The cheapest cost from `start` to every node reachable from it.
+pub fn dijkstra_all<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut heap = BinaryHeap::from([Reverse((zero, 0))]);
+    let mut visited = Visited::new(start, zero);
+
+    while let Some(Reverse((cost, i))) = heap.pop() {
+        if cost > visited.best[i].0 {
+            continue;
+        }
+        for (next, step) in successors(&visited.nodes[i]) {
+            if let Some(j) = visited.relax(next, cost + step, i) {
+                heap.push(Reverse((cost + step, j)));
+            }
+        }
+    }
+
+    visited
+        .nodes
+        .into_iter()
+        .zip(visited.best)
+        .map(|(node, (cost, _))| (node, cost))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small weighted graph where the direct edges are never the cheapest route.
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 7), ('c', 2), ('d', 20)],
+            'b' => vec![('d', 1)],
+            'c' => vec![('b', 3), ('e', 10)],
+            'd' => vec![('e', 2)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let (path, cost) = dijkstra('a', edges, |&n| n == 'e').unwrap();
+        assert_eq!(path, vec!['a', 'c', 'b', 'd', 'e']);
+        assert_eq!(cost, 8);
+
+        assert_eq!(dijkstra('a', edges, |&n| n == 'a'), Some((vec!['a'], 0)));
+        assert_eq!(dijkstra('b', edges, |&n| n == 'a'), None);
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let costs = dijkstra_all('a', edges);
+        assert_eq!(
+            costs,
+            HashMap::from([('a', 0), ('b', 5), ('c', 2), ('d', 6), ('e', 8)])
+        );
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        // Walk around a wall on an open plane, with Manhattan distance as the heuristic
+        let wall = |x: i32, y: i32| x == 2 && (0..4).contains(&y);
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (-5..10).contains(&x) && (-5..10).contains(&y) && !wall(x, y))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let goal = (4, 0);
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x).abs() + (goal.1 - y).abs();
+
+        let (path, cost) = astar((0, 0), successors, heuristic, |&p| p == goal).unwrap();
+        // Over the top of the wall is 6 steps, round the bottom would be 10
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert!(path
+            .windows(2)
+            .all(|w| (w[0].0 - w[1].0).abs() + (w[0].1 - w[1].1).abs() == 1));
+        assert!(path.iter().all(|&(x, y)| !wall(x, y)));
+
+        let (_, dijkstra_cost) = dijkstra((0, 0), successors, |&p| p == goal).unwrap();
+        assert_eq!(dijkstra_cost, cost);
+    }
+}
@@ -0,0 +1,10 @@
+//! Searches over implicit graphs: nodes are any hashable value and edges come from a successor
+//! closure, so a puzzle never has to build an adjacency list up front.
+
+pub mod bfs;
+pub mod dijkstra;
+pub mod scc;
+
+pub use bfs::{bfs, flood_fill};
+pub use dijkstra::{astar, dijkstra, dijkstra_all};
+pub use scc::strongly_connected_components;
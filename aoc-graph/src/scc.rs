@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Per-node bookkeeping for Tarjan's algorithm.
+struct NodeState {
+    order: usize,
+    low: usize,
+    on_stack: bool,
+}
+
+/// A node being explored, its successors, and how many of them have been looked at.
+type Frame = (usize, Vec<usize>, usize);
+
+struct Tarjan<N, F> {
+    successors: F,
+    ids: HashMap<N, usize>,
+    nodes: Vec<N>,
+    state: Vec<Option<NodeState>>,
+    stack: Vec<usize>,
+    counter: usize,
+}
+
+impl<N, F, I> Tarjan<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn id(&mut self, node: N) -> usize {
+        *self.ids.entry(node).or_insert_with_key(|node| {
+            self.nodes.push(node.clone());
+            self.state.push(None);
+            self.nodes.len() - 1
+        })
+    }
+
+    fn visit(&mut self, v: usize) -> Frame {
+        self.state[v] = Some(NodeState {
+            order: self.counter,
+            low: self.counter,
+            on_stack: true,
+        });
+        self.counter += 1;
+        self.stack.push(v);
+        let next: Vec<N> = (self.successors)(&self.nodes[v]).into_iter().collect();
+        let next = next.into_iter().map(|node| self.id(node)).collect();
+        (v, next, 0)
+    }
+
+    fn state(&mut self, v: usize) -> &mut NodeState {
+        self.state[v].as_mut().unwrap()
+    }
+
+    /// Explores everything reachable from `root`, adding the components it finishes.
+    fn run(&mut self, root: usize, components: &mut Vec<Vec<N>>) {
+        let mut frames = vec![self.visit(root)];
+        while let Some((v, next, seen)) = frames.last_mut() {
+            let v = *v;
+            if let Some(&w) = next.get(*seen) {
+                *seen += 1;
+                match &self.state[w] {
+                    None => frames.push(self.visit(w)),
+                    Some(state) if state.on_stack => {
+                        let order = state.order;
+                        let state = self.state(v);
+                        state.low = state.low.min(order);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            frames.pop();
+            let low = self.state(v).low;
+            if low == self.state(v).order {
+                let mut component = Vec::new();
+                loop {
+                    let w = self.stack.pop().unwrap();
+                    self.state(w).on_stack = false;
+                    component.push(self.nodes[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+            if let Some(&(parent, _, _)) = frames.last() {
+                let state = self.state(parent);
+                state.low = state.low.min(low);
+            }
+        }
+    }
+}
+
+/// Splits the graph reachable from `nodes` into strongly connected components.
+///
+/// Uses Tarjan's algorithm with an explicit stack, so deep graphs can't overflow the call
+/// stack. Components come out in reverse topological order: nothing in a component leads to
+/// one listed after it.
+pub fn strongly_connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut tarjan = Tarjan {
+        successors,
+        ids: HashMap::new(),
+        nodes: Vec::new(),
+        state: Vec::new(),
+        stack: Vec::new(),
+        counter: 0,
+    };
+    let roots: Vec<usize> = nodes.into_iter().map(|node| tarjan.id(node)).collect();
+
+    let mut components = Vec::new();
+    for root in roots {
+        if tarjan.state[root].is_none() {
+            tarjan.run(root, &mut components);
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut components: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+        for component in &mut components {
+            component.sort();
+        }
+        components
+    }
+
+    #[test]
+    fn test_components() {
+        // 1 -> 2 -> 3 -> 1 is a cycle that leads into 4 <-> 5, with 6 on its own
+        let edges = |n: &u32| match n {
+            1 => vec![2],
+            2 => vec![3],
+            3 => vec![1, 4],
+            4 => vec![5],
+            5 => vec![4],
+            _ => vec![],
+        };
+        let components = sorted(strongly_connected_components([1, 6], edges));
+        assert_eq!(components, vec![vec![4, 5], vec![1, 2, 3], vec![6]]);
+    }
+
+    #[test]
+    fn test_reaches_nodes_not_listed() {
+        let edges = |n: &u32| if *n < 5 { vec![n + 1] } else { vec![] };
+        let components = strongly_connected_components([0], edges);
+        // a chain: every node alone, and the end of the chain first
+        assert_eq!(
+            components,
+            vec![vec![5], vec![4], vec![3], vec![2], vec![1], vec![0]]
+        );
+    }
+
+    #[test]
+    fn test_deep_cycle() {
+        // deep enough to overflow a recursive implementation
+        let n = 200_000;
+        let edges = move |i: &u32| vec![(i + 1) % n];
+        let components = strongly_connected_components([0], edges);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), n as usize);
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use aoc_common::AocError;
+
+/// Offsets to the four orthogonal neighbours: up, right, down, left.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Offsets to all eight neighbours, clockwise from up.
+const ALL: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid stored row by row in a single `Vec`.
+///
+/// Cells are addressed as `(x, y)`, with `x` the column and `y` the row, both counting from the
+/// top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row order.
+    ///
+    /// Panics if `cells` doesn't split evenly into rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't fill rows {} wide",
+            cells.len(),
+            width
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, one row per line.
+    ///
+    /// `tile` returns `None` for characters that don't belong in the grid. Unknown characters,
+    /// rows of the wrong width and empty input are reported with their position in `input`.
+    pub fn parse(input: &str, mut tile: impl FnMut(char) -> Option<T>) -> Result<Self, AocError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(AocError::at(input, input, "empty grid"));
+        }
+
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines() {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                match tile(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        let token = &line[i..i + c.len_utf8()];
+                        return Err(AocError::at(input, token, "unknown tile"));
+                    }
+                }
+            }
+            if cells.len() - start != width {
+                return Err(AocError::at(
+                    input,
+                    line,
+                    format!("expected a row {} wide", width),
+                ));
+            }
+        }
+        Ok(Grid::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Like [`Grid::get`], but for coordinates that may have stepped off the top or left edge.
+    pub fn get_signed(&self, x: isize, y: isize) -> Option<&T> {
+        let (x, y) = self.checked(x, y)?;
+        self.get(x, y)
+    }
+
+    /// Converts signed coordinates to a position, if they are inside the grid.
+    pub fn checked(&self, x: isize, y: isize) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// The in-bounds orthogonal neighbours of `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `(x, y)`.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &ALL)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.checked(x as isize + dx, y as isize + dy))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width)
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_exact_mut(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Every cell with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, in row order, matching `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.cells.iter().map(f).collect())
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Builds a `width` by `height` grid by calling `f` with each source position.
+    fn remap(&self, width: usize, height: usize, f: impl Fn(usize, usize) -> (usize, usize)) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| {
+                let (x, y) = f(i % width, i / width);
+                self[(x, y)].clone()
+            })
+            .collect();
+        Grid::from_vec(width, cells)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid::from_vec(width, vec![fill; width * height])
+    }
+
+    /// Swaps rows and columns, mirroring along the top-left to bottom-right diagonal.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.remap(self.width, self.height, |x, y| (width - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.remap(self.width, self.height, |x, y| (x, height - 1 - y))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.index_of(x, y) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        match self.index_of(x, y) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "({}, {}) is outside a {}x{} grid",
+                x, y, self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Prints one line per row with no separators between cells, the same layout `parse` reads.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = chars("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 0)], 'c');
+        assert_eq!(grid.get(0, 1), Some(&'d'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse("#.\n.x", |c| (c != 'x').then_some(c)).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (2, 2, "x")
+        );
+
+        let error = Grid::parse("##\n#", Some).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (2, 1, "#")
+        );
+
+        assert!(Grid::parse("", Some).is_err());
+    }
+
+    #[test]
+    fn test_get_signed() {
+        let grid = chars("ab\ncd");
+        assert_eq!(grid.get_signed(1, 1), Some(&'d'));
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+        assert_eq!(grid.get_signed(2, 0), None);
+        assert_eq!(grid.checked(0, 1), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+        assert_eq!(
+            grid.neighbours8(2, 2).collect::<Vec<_>>(),
+            vec![(2, 1), (1, 2), (1, 1)]
+        );
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let mut grid = chars("abc\ndef");
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        grid.row_mut(0).reverse();
+        assert_eq!(grid.to_string(), "cba\ndef");
+    }
+
+    #[test]
+    fn test_find_and_iter() {
+        let grid = chars("..\n.S");
+        assert_eq!(grid.find(|&c| c == 'S'), Some((1, 1)));
+        assert_eq!(grid.find(|&c| c == 'X'), None);
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, &c)| c == '.')
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = chars("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = chars("#.\n.#").map(|&c| c == '#');
+        assert_eq!(grid.iter().filter(|(_, &b)| b).count(), 2);
+        assert!(grid[(1, 1)]);
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// The half-open range of values `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both intervals, if there are any.
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+impl Interval<u64> {
+    /// The `len` values starting at `start`, unless they would run past `u64::MAX`.
+    pub fn from_len(start: u64, len: u64) -> Option<Self> {
+        Some(Interval::new(start, start.checked_add(len)?))
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let a = Interval::new(0, 10);
+        assert_eq!(
+            a.intersect(&Interval::new(5, 15)),
+            Some(Interval::new(5, 10))
+        );
+        assert_eq!(a.intersect(&Interval::new(2, 3)), Some(Interval::new(2, 3)));
+        // half-open, so touching intervals share nothing
+        assert_eq!(a.intersect(&Interval::new(10, 15)), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        let a = Interval::from_len(79, 14).unwrap();
+        assert_eq!(a, Interval::new(79, 93));
+        assert_eq!(a.len(), 14);
+        assert!(a.contains(79));
+        assert!(a.contains(92));
+        assert!(!a.contains(93));
+        assert!(Interval::new(3, 3).is_empty());
+    }
+
+    #[test]
+    fn test_from_len_overflow() {
+        assert_eq!(
+            Interval::from_len(u64::MAX - 1, 1),
+            Some(Interval::new(u64::MAX - 1, u64::MAX))
+        );
+        assert_eq!(Interval::from_len(u64::MAX - 1, 2), None);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod interval;
+pub mod set;
+pub mod shift;
+
+pub use interval::Interval;
+pub use set::IntervalSet;
+pub use shift::{split, Shift};
@@ -0,0 +1,226 @@
+use std::fmt;
+
+use crate::Interval;
+
+/// A set of values stored as sorted, disjoint intervals.
+///
+/// Overlapping or touching intervals are merged as they are added, so two sets holding the same
+/// values always compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts and merges arbitrary intervals, dropping empty ones.
+    fn normalize(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_unstable();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = Self::normalize(intervals);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The disjoint intervals making up the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        Self::normalize(self.iter().chain(other.iter()).copied().collect())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            result.extend(a.intersect(b));
+            // Whichever ends first can't overlap anything further along the other set
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+
+    /// The values in `self` that aren't in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = Vec::new();
+        let mut j = 0;
+        for &interval in &self.intervals {
+            let mut start = interval.start;
+            // Skip the parts of `other` entirely before this interval
+            while other.intervals.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(b) = other.intervals.get(k).filter(|b| b.start < interval.end) {
+                if b.start > start {
+                    result.push(Interval::new(start, b.start));
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < interval.end {
+                result.push(Interval::new(start, interval.end));
+            }
+        }
+        IntervalSet { intervals: result }
+    }
+}
+
+impl IntervalSet<u64> {
+    /// How many values the set holds.
+    pub fn count(&self) -> u64 {
+        self.iter().map(Interval::len).sum()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl<T: Ord + Copy> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalize(vec![interval])
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet<u64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let s = set(&[(5, 8), (0, 2), (1, 3), (3, 4), (9, 9)]);
+        assert_eq!(s.to_string(), "{0..4, 5..8}");
+        assert_eq!(s.count(), 7);
+        assert_eq!(s.min(), Some(0));
+        assert!(s.contains(3));
+        assert!(!s.contains(4));
+        assert!(s.contains(7));
+        assert!(!s.contains(8));
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut s = set(&[(0, 2), (6, 8)]);
+        s.insert(Interval::new(2, 6));
+        assert_eq!(s, set(&[(0, 8)]));
+        s.insert(Interval::new(10, 12));
+        assert_eq!(s.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_union() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 11), (20, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 15), (20, 25)]));
+        assert_eq!(a.union(&IntervalSet::new()), a);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = set(&[(0, 5), (10, 15), (20, 30)]);
+        let b = set(&[(3, 12), (14, 22)]);
+        assert_eq!(
+            a.intersection(&b),
+            set(&[(3, 5), (10, 12), (14, 15), (20, 22)])
+        );
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_difference() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(2, 4), (6, 8), (9, 22), (25, 26)]);
+        assert_eq!(
+            a.difference(&b),
+            set(&[(0, 2), (4, 6), (8, 9), (22, 25), (26, 30)])
+        );
+        assert_eq!(b.difference(&a), set(&[(10, 20)]));
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let a = set(&[(1, 4), (6, 7), (9, 15), (17, 18)]);
+        let b = set(&[(0, 2), (3, 10), (12, 13), (16, 20)]);
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+        for value in 0..25 {
+            let (in_a, in_b) = (a.contains(value), b.contains(value));
+            assert_eq!(union.contains(value), in_a || in_b, "{}", value);
+            assert_eq!(intersection.contains(value), in_a && in_b, "{}", value);
+            assert_eq!(difference.contains(value), in_a && !in_b, "{}", value);
+        }
+    }
+}
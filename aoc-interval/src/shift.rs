@@ -0,0 +1,114 @@
+use crate::{Interval, IntervalSet};
+
+/// Moves the values in `source` so that `source.start` lands on `dest`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shift {
+    pub source: Interval<u64>,
+    pub dest: u64,
+}
+
+impl Shift {
+    /// `None` if the moved values would run past `u64::MAX`.
+    pub fn new(source: Interval<u64>, dest: u64) -> Option<Self> {
+        dest.checked_add(source.len())?;
+        Some(Shift { source, dest })
+    }
+
+    /// Where `value` moves to, if it is in this shift's source.
+    pub fn apply(&self, value: u64) -> Option<u64> {
+        self.source
+            .contains(value)
+            .then(|| self.dest + (value - self.source.start))
+    }
+
+    /// Moves `interval`, which must lie within the source. [`Shift::new`] checked it fits.
+    fn apply_interval(&self, interval: Interval<u64>) -> Interval<u64> {
+        let start = self.dest + (interval.start - self.source.start);
+        Interval::new(start, start + interval.len())
+    }
+}
+
+/// Splits `interval` against `shifts`, whose sources shouldn't overlap.
+///
+/// Returns where the pieces covered by a shift moved to, and the pieces no shift covered.
+pub fn split(interval: Interval<u64>, shifts: &[Shift]) -> (IntervalSet<u64>, IntervalSet<u64>) {
+    let mapped = shifts
+        .iter()
+        .filter_map(|shift| {
+            let overlap = interval.intersect(&shift.source)?;
+            Some(shift.apply_interval(overlap))
+        })
+        .collect();
+    let covered: IntervalSet<u64> = shifts.iter().map(|shift| shift.source).collect();
+    let unmapped = IntervalSet::from(interval).difference(&covered);
+    (mapped, unmapped)
+}
+
+impl IntervalSet<u64> {
+    /// Sends every value through `shifts`, leaving values no shift covers where they are.
+    pub fn apply_shifts(&self, shifts: &[Shift]) -> IntervalSet<u64> {
+        self.iter()
+            .map(|&interval| {
+                let (mapped, unmapped) = split(interval, shifts);
+                mapped.union(&unmapped)
+            })
+            .fold(IntervalSet::new(), |acc, moved| acc.union(&moved))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // seed-to-soil from the day 5 example: 98..100 -> 50.., 50..98 -> 52..
+    fn seed_to_soil() -> Vec<Shift> {
+        vec![
+            Shift::new(Interval::from_len(98, 2).unwrap(), 50).unwrap(),
+            Shift::new(Interval::from_len(50, 48).unwrap(), 52).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_apply() {
+        let shifts = seed_to_soil();
+        assert_eq!(shifts[0].apply(99), Some(51));
+        assert_eq!(shifts[0].apply(100), None);
+        assert_eq!(shifts[1].apply(79), Some(81));
+    }
+
+    #[test]
+    fn test_new_overflow() {
+        let source = Interval::new(0, 10);
+        assert!(Shift::new(source, u64::MAX - 10).is_some());
+        assert!(Shift::new(source, u64::MAX - 9).is_none());
+    }
+
+    #[test]
+    fn test_split() {
+        let (mapped, unmapped) = split(Interval::new(40, 99), &seed_to_soil());
+        // 40..50 isn't covered; 50..98 moves up by 2 and 98..99 down to 50..51
+        assert_eq!(mapped.to_string(), "{50..51, 52..100}");
+        assert_eq!(unmapped.to_string(), "{40..50}");
+
+        let (mapped, unmapped) = split(Interval::new(0, 10), &seed_to_soil());
+        assert!(mapped.is_empty());
+        assert_eq!(unmapped, IntervalSet::from(Interval::new(0, 10)));
+    }
+
+    #[test]
+    fn test_apply_shifts() {
+        let seeds: IntervalSet<u64> = [Interval::new(79, 93), Interval::new(55, 68)]
+            .into_iter()
+            .collect();
+        let soil = seeds.apply_shifts(&seed_to_soil());
+        assert_eq!(soil.to_string(), "{57..70, 81..95}");
+        assert_eq!(soil.count(), seeds.count());
+        for seed in 0..110 {
+            let expected = seed_to_soil()
+                .iter()
+                .find_map(|shift| shift.apply(seed))
+                .unwrap_or(seed);
+            assert_eq!(soil.contains(expected), seeds.contains(seed), "{}", seed);
+        }
+    }
+}
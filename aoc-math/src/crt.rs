@@ -0,0 +1,148 @@
+use crate::divisors::gcd_u128;
+
+/// `(a + b) % m` for `a, b < m`, without overflowing.
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `(a - b) % m` for `a, b < m`.
+fn sub_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// `(a * b) % m`, falling back to shift-and-add when the product doesn't fit in a `u128`.
+pub fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    let (mut a, mut b) = (a % m, b % m);
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// The `x < m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: u128, m: u128) -> Option<u128> {
+    if m == 1 {
+        return Some(0);
+    }
+    // Extended Euclid, keeping the coefficient of `a` reduced mod `m` so it stays unsigned
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut s0, mut s1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, sub_mod(s0, mul_mod(q, s1, m), m));
+    }
+    (r0 == 1).then_some(s0)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)`, given as `(residue, modulus)`.
+///
+/// The moduli don't need to be coprime. Returns the smallest non-negative solution together
+/// with the modulus it repeats at (the lcm of all the moduli), or `None` if the congruences
+/// contradict each other or that lcm overflows a `u128`. Panics if any modulus is zero.
+pub fn crt(congruences: impl IntoIterator<Item = (u128, u128)>) -> Option<(u128, u128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            assert!(m2 > 0, "modulus must be positive");
+            let r2 = r2 % m2;
+            let g = gcd_u128(m1, m2);
+            if r1 % g != r2 % g {
+                return None;
+            }
+            let lcm = (m1 / g).checked_mul(m2)?;
+
+            // x = r1 + m1 * k, where (m1 / g) * k ≡ (r2 - r1) / g (mod m2 / g)
+            let m2g = m2 / g;
+            let step = sub_mod(r2, r1 % m2, m2) / g;
+            let k = mul_mod(step, mod_inverse(m1 / g, m2g)?, m2g);
+            let x = add_mod(r1, mul_mod(m1, k, lcm), lcm);
+            Some((x, lcm))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_mod() {
+        assert_eq!(mul_mod(7, 8, 5), 1);
+        let m = u128::MAX - 158; // a large prime
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(u128::MAX, 2, u128::MAX - 1), 2);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(4, 8), None);
+        let m = u128::MAX - 158;
+        let inverse = mod_inverse(12345, m).unwrap();
+        assert_eq!(mul_mod(12345, inverse, m), 1);
+    }
+
+    #[test]
+    fn test_crt_coprime() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(0, 4), (0, 6)]), Some((0, 12)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_crt_non_coprime() {
+        // x ≡ 3 (mod 4) and x ≡ 5 (mod 6) agree mod 2
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        // but x ≡ 1 (mod 4) and x ≡ 2 (mod 6) don't
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        // residues bigger than their modulus are reduced first
+        assert_eq!(crt([(10, 4), (8, 6)]), Some((2, 12)));
+    }
+
+    #[test]
+    fn test_crt_brute_force() {
+        for m1 in 1..12u128 {
+            for m2 in 1..12u128 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let expected = (0..m1 * m2).find(|x| x % m1 == r1 && x % m2 == r2);
+                        let got = crt([(r1, m1), (r2, m2)]).map(|(x, _)| x);
+                        assert_eq!(
+                            got, expected,
+                            "x ≡ {} (mod {}), x ≡ {} (mod {})",
+                            r1, m1, r2, m2
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt_large() {
+        let p = u64::MAX as u128 - 58; // the largest prime below 2^64
+        let q = u64::MAX as u128 - 82;
+        let (x, m) = crt([(1, p), (2, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (1, 2));
+
+        assert_eq!(crt([(0, u128::MAX), (0, u128::MAX - 1)]), None);
+    }
+}
@@ -0,0 +1,62 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    gcd_u128(a.into(), b.into()) as u64
+}
+
+/// [`gcd`] for `u128`.
+pub fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Panics if the result doesn't fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .unwrap_or_else(|| panic!("lcm({}, {}) overflows u64", a, b))
+}
+
+/// The greatest common divisor of every value, or 0 if there are none.
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// The least common multiple of every value, or 1 if there are none.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(1, lcm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(18, 12), 6);
+        assert_eq!(gcd(7, 13), 1);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd_all([24, 36, 60]), 12);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(gcd_u128(u128::MAX, 5), 5);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_all([2, 3, 4, 5]), 60);
+        assert_eq!(lcm_all([]), 1);
+        assert_eq!(lcm(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn test_lcm_overflow() {
+        lcm(u64::MAX, u64::MAX - 1);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod crt;
+pub mod divisors;
+pub mod roots;
+
+pub use crt::{crt, mod_inverse, mul_mod};
+pub use divisors::{gcd, gcd_all, gcd_u128, lcm, lcm_all};
+pub use roots::{exact_sqrt, isqrt, isqrt_u128, quadratic_below_zero};
@@ -0,0 +1,96 @@
+use std::ops::RangeInclusive;
+
+/// The largest `r` with `r * r <= n`, computed without going through floating point.
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+/// [`isqrt`] for `u128`.
+pub fn isqrt_u128(n: u128) -> u128 {
+    n.isqrt()
+}
+
+/// The square root of `n`, if it is a perfect square.
+pub fn exact_sqrt(n: u128) -> Option<u128> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
+}
+
+/// The integers `x` where `a * x^2 + b * x + c < 0`, for `a > 0`.
+///
+/// They always form a single run between the two roots, or there are none. Found with exact
+/// integer arithmetic, so roots that land on an integer are excluded rather than rounded in.
+pub fn quadratic_below_zero(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    assert!(a > 0, "the parabola must open upwards");
+    let discriminant = b * b - 4 * a * c;
+    if discriminant <= 0 {
+        return None;
+    }
+    let f = |x: i128| (a * x + b) * x + c;
+
+    // sqrt(discriminant) lies in [s, s + 1), so these are just outside the real roots
+    let s = isqrt_u128(discriminant as u128) as i128;
+    let mut low = (-b - s - 1).div_euclid(2 * a);
+    let mut high = (-b + s + 1).div_euclid(2 * a) + 1;
+    while low <= high && f(low) >= 0 {
+        low += 1;
+    }
+    while high >= low && f(high) >= 0 {
+        high -= 1;
+    }
+    (low <= high).then_some(low..=high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        // as an f64 this rounds up to 2^64, whose root is one too big
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_exact_sqrt() {
+        assert_eq!(exact_sqrt(49), Some(7));
+        assert_eq!(exact_sqrt(50), None);
+        let big = u64::MAX as u128;
+        assert_eq!(exact_sqrt(big * big), Some(big));
+        assert_eq!(exact_sqrt(big * big - 1), None);
+    }
+
+    #[test]
+    fn test_quadratic_below_zero() {
+        // (x - 2)(x - 5) < 0
+        assert_eq!(quadratic_below_zero(1, -7, 10), Some(3..=4));
+        // x^2 - 2 < 0
+        assert_eq!(quadratic_below_zero(1, 0, -2), Some(-1..=1));
+        // (2x - 1)(2x - 3) < 0 only between 0.5 and 1.5
+        assert_eq!(quadratic_below_zero(4, -8, 3), Some(1..=1));
+        // (5x - 1)(5x - 4) < 0 between 0.2 and 0.8, which holds no integer
+        assert_eq!(quadratic_below_zero(25, -25, 4), None);
+        // (x - 3)^2 never dips below zero
+        assert_eq!(quadratic_below_zero(1, -6, 9), None);
+    }
+
+    #[test]
+    fn test_quadratic_brute_force() {
+        for a in 1..4 {
+            for b in -20..20 {
+                for c in -20..20 {
+                    let expected: Vec<i128> =
+                        (-50..50).filter(|&x| a * x * x + b * x + c < 0).collect();
+                    let got: Vec<i128> = quadratic_below_zero(a, b, c)
+                        .map(|range| range.collect())
+                        .unwrap_or_default();
+                    assert_eq!(got, expected, "{}x^2 + {}x + {}", a, b, c);
+                }
+            }
+        }
+    }
+}
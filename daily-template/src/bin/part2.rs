@@ -1,12 +1,15 @@
 use {{crate_name}}::part2::process;
 
-
 #[tracing::instrument]
 fn main() {
-    let file = include_str!("../../input.txt");
-
-
-    let result = process(file);
-    println!("{}", result);
-
+    let _guard = aoc_common::trace::init();
+    let file = aoc_common::input::from_args(env!("CARGO_MANIFEST_DIR"), 2);
+
+    match process(&file) {
+        Ok(result) => println!("{}", result),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,21 @@
+//! Made-up puzzle inputs, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "lines";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1_000, 4_000, 16_000];
+
+/// `size` lines of random letters, until it makes something shaped like the real input.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let lines: Vec<String> = (0..size)
+        .map(|_| {
+            (0..rng.range(1..40))
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+    lines.join("\n")
+}
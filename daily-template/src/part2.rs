@@ -1,15 +1,47 @@
-pub fn process(_input: &str) -> String {
-    println!("hi part 2");
-    "part 2".to_string()
+use aoc_common::{Answer, AocError, Solution};
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    Ok(input.lines().collect())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: Vec<&str>) -> Answer {
+    lines.len().into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = {{day}};
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../example.txt");
+
     #[test]
-    fn test_process() {
-        let input = "";
-        assert_eq!("", process(input))
+    #[ignore = "paste the example and its answer"]
+    fn test_process() -> Result<(), AocError> {
+        // Paste the puzzle's example into example.txt and its answer here, then drop the ignore
+        assert_eq!("0", process(EXAMPLE)?);
+        Ok(())
     }
 }
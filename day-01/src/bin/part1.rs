@@ -2,8 +2,14 @@ use day_01::part1::process;
 
 #[tracing::instrument]
 fn main() {
-    let file = include_str!("../../input1.txt");
+    let _guard = aoc_common::trace::init();
+    let file = aoc_common::input::from_args(env!("CARGO_MANIFEST_DIR"), 1);
 
-    let result = process(file);
-    println!("{}", result);
+    match process(&file) {
+        Ok(result) => println!("{}", result),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,33 @@
+//! Made-up calibration documents, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "characters per line";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[25, 50, 100, 200, 400];
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// 200 lines of about `size` letters, digits and spelled-out digits, each with at least one digit.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let lines: Vec<String> = (0..200)
+        .map(|_| {
+            let mut line = String::new();
+            while line.len() + 1 < size {
+                match rng.below(10) {
+                    0 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                    1 => line.push_str(rng.pick::<&str>(&WORDS)),
+                    _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+                }
+            }
+            let at = rng.below(line.len() + 1);
+            line.insert(at, char::from(b'1' + rng.below(9) as u8));
+            line
+        })
+        .collect();
+    lines.join("\n")
+}
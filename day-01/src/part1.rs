@@ -1,25 +1,52 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
+use aoc_common::{Answer, AocError, Solution};
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            let digits: Vec<u32> = line.chars().filter_map(|c| c.to_digit(10)).collect();
+            if digits.is_empty() {
+                return Err(AocError::at(input, line, "line has no digits"));
+            }
+            Ok(digits)
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: Vec<Vec<u32>>) -> Answer {
     let mut sum = 0;
-    for line in _input.split('\n') {
-        let first_digit = line
-            .chars()
-            .find(|c| c.is_ascii_digit())
-            .unwrap()
-            .to_digit(10)
-            .unwrap();
-        let last_digit = line
-            .chars()
-            .rev()
-            .find(|c| c.is_ascii_digit())
-            .unwrap()
-            .to_digit(10)
-            .unwrap();
+    for digits in lines {
+        let first_digit = digits.first().unwrap();
+        let last_digit = digits.last().unwrap();
 
         // dbg!(first_digit, last_digit);
         sum += first_digit * 10 + last_digit
     }
-    sum.to_string()
+    sum.into()
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part1::process(_input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 1;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -27,11 +54,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "1abc2\n\
                     pqr3stu8vwx\n\
                     a1b2c3d4e5f\n\
                     treb7uchet";
-        assert_eq!("142", process(input))
+        assert_eq!("142", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_windows_file() -> Result<(), AocError> {
+        assert_eq!(parse("1abc2\r\ntreb7uchet\r\n")?, vec![vec![1, 2], vec![7]]);
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+use aoc_common::{Answer, AocError, Solution};
+
 #[tracing::instrument]
 fn find_number(substring: &str) -> Option<u32> {
     let number_list = [
@@ -44,13 +46,50 @@ fn process_line(line: &str) -> u32 {
     first * 10 + last
 }
 
-pub fn process(_input: &str) -> String {
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    input
+        .lines()
+        .map(|line| match find_number(line) {
+            Some(_) => Ok(line),
+            None => Err(AocError::at(
+                input,
+                line,
+                "line has no digits or digit words",
+            )),
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: Vec<&str>) -> Answer {
     let mut sum = 0;
-    for line in _input.split('\n') {
+    for line in lines {
         sum += process_line(line);
     }
 
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part2::process(_input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 1;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -93,7 +132,7 @@ mod tests {
     }
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "two1nine\n\
             eightwothree3\n\
             abcone2threexyz\n\
@@ -101,6 +140,7 @@ mod tests {
             4nineeightseven2\n\
             zoneight234\n\
             7pqrstsixteen";
-        assert_eq!("281", process(input))
+        assert_eq!("281", process(input)?);
+        Ok(())
     }
 }
@@ -0,0 +1,31 @@
+//! Made-up cube games, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "games";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1_000, 4_000, 16_000, 64_000];
+
+/// `size` games of one to six handfuls, each showing one to three colours.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let games: Vec<String> = (1..=size)
+        .map(|id| {
+            let handfuls: Vec<String> = (0..rng.range(1..7))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    rng.shuffle(&mut colors);
+                    let shown = rng.range(1..4) as usize;
+                    colors[..shown]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.range(1..21), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect();
+            format!("Game {}: {}", id, handfuls.join("; "))
+        })
+        .collect();
+    games.join("\n")
+}
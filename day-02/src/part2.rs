@@ -1,5 +1,8 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+
 #[derive(Debug, PartialEq)]
-struct Rgb {
+pub struct Rgb {
     red: u32,
     green: u32,
     blue: u32,
@@ -11,41 +14,47 @@ impl Rgb {
     }
 }
 
-fn parse_color_group(group: &str) -> Rgb {
-    group
-        .split(',')
-        .map(|s| s.trim())
-        .map(|s| s.split(' '))
-        .map(|mut s| (s.next().unwrap(), s.next().unwrap()))
-        .fold(
-            Rgb {
-                red: 0,
-                green: 0,
-                blue: 0,
-            },
-            |mut acc, (v, k)| {
-                match k {
-                    "red" => acc.red = v.parse().unwrap(),
-                    "green" => acc.green = v.parse().unwrap(),
-                    "blue" => acc.blue = v.parse().unwrap(),
-                    _ => panic!("Invalid color: {}", k),
-                }
-                acc
-            },
-        )
+fn parse_color_group(input: &str, group: &str) -> Result<Rgb, AocError> {
+    let mut rgb = Rgb {
+        red: 0,
+        green: 0,
+        blue: 0,
+    };
+    for cubes in group.split(',').map(|s| s.trim()) {
+        let Some((v, k)) = cubes.split_once(' ') else {
+            return Err(AocError::at(input, cubes, "expected `<count> <color>`"));
+        };
+        let count = number(input, v)?;
+        match k {
+            "red" => rgb.red = count,
+            "green" => rgb.green = count,
+            "blue" => rgb.blue = count,
+            _ => return Err(AocError::at(input, k, "unknown color")),
+        }
+    }
+    Ok(rgb)
 }
 
-#[tracing::instrument]
-pub fn process(_input: &str) -> String {
-    let mut output = 0;
-    for line in _input.split('\n') {
-        let colors = line.split_once(':').unwrap().1.trim();
-        let rgbs: Vec<Rgb> = colors
-            .split(';')
-            .map(|s| s.trim())
-            .map(parse_color_group)
-            .collect();
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Vec<Rgb>>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, colors) = line
+                .split_once(':')
+                .ok_or_else(|| AocError::at(input, line, "expected `Game <id>:`"))?;
+            colors
+                .split(';')
+                .map(|s| parse_color_group(input, s.trim()))
+                .collect()
+        })
+        .collect()
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(games: Vec<Vec<Rgb>>) -> Answer {
+    let mut output = 0;
+    for rgbs in games {
         let peak = rgbs.iter().fold(
             Rgb {
                 red: 0,
@@ -61,7 +70,29 @@ pub fn process(_input: &str) -> String {
         output += peak.power();
     }
 
-    output.to_string()
+    output.into()
+}
+
+#[tracing::instrument]
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part2::process(_input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 2;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<Vec<Rgb>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -69,45 +100,54 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
                            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
                            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n\
                            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\n\
                            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!("2286", process(input))
+        assert_eq!("2286", process(input)?);
+        Ok(())
     }
 
     #[test]
     fn test_parse_color_group() {
         assert_eq!(
-            parse_color_group("1 blue, 2 green"),
-            Rgb {
+            parse_color_group("1 blue, 2 green", "1 blue, 2 green"),
+            Ok(Rgb {
                 red: 0,
                 green: 2,
                 blue: 1
-            }
+            })
         );
 
         assert_eq!(
-            parse_color_group("3 green, 4 blue, 1 red"),
-            Rgb {
+            parse_color_group("3 green, 4 blue, 1 red", "3 green, 4 blue, 1 red"),
+            Ok(Rgb {
                 red: 1,
                 green: 3,
                 blue: 4
-            }
+            })
         );
 
         assert_eq!(
-            parse_color_group(" 3 green, 15 blue, 14 red"),
-            Rgb {
+            parse_color_group(" 3 green, 15 blue, 14 red", " 3 green, 15 blue, 14 red"),
+            Ok(Rgb {
                 red: 14,
                 green: 3,
                 blue: 15
-            }
+            })
         );
     }
 
+    #[test]
+    fn test_unknown_color() {
+        let input = "Game 1: 3 blue\nGame 2: 4 purple, 1 red";
+        let error = parse(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 11));
+        assert_eq!(error.token, "purple");
+    }
+
     #[test]
     fn test_power() {
         assert_eq!(
@@ -0,0 +1,38 @@
+//! Made-up engine schematics, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "grid side";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[70, 140, 280, 560];
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '&', '-'];
+
+/// A `size` by `size` schematic of part numbers and symbols, with plenty of `*`s.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let rows: Vec<String> = (0..size)
+        .map(|_| {
+            let mut row = String::new();
+            while row.len() < size {
+                let roll = rng.below(100);
+                let digits = rng.range(1..4) as usize;
+                if roll < 8 && row.len() + digits < size {
+                    let number = rng.range(10u64.pow(digits as u32 - 1)..10u64.pow(digits as u32));
+                    row.push_str(&number.to_string());
+                    // Keep the next number from running into this one
+                    row.push('.');
+                } else if roll < 10 {
+                    row.push('*');
+                } else if roll < 13 {
+                    row.push(*rng.pick(&SYMBOLS));
+                } else {
+                    row.push('.');
+                }
+            }
+            row
+        })
+        .collect();
+    rows.join("\n")
+}
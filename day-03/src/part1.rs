@@ -1,3 +1,7 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+use aoc_grid::Grid;
+
 struct Number {
     number: u32,
     x: i32,
@@ -5,20 +9,16 @@ struct Number {
     tagged: bool,
 }
 
-fn get_surrounding_squares_tag(grid: &[Vec<char>], number: &mut Number) {
+fn get_surrounding_squares_tag(grid: &Grid<char>, number: &mut Number) {
     let size = number.number.to_string().len() as i32;
     let symbols = ['*', '-', '+', '#', '&', '$', '=', '%', '@', '/'];
 
     for i in number.y - 1..=number.y + 1 {
-        if i < 0 || i >= grid.len() as i32 {
-            continue;
-        }
-
         for j in number.x - 1..=number.x + size {
-            if j < 0 || j >= grid[0].len() as i32 {
-                continue;
-            }
-            if symbols.contains(&grid[i as usize][j as usize]) {
+            if grid
+                .get_signed(j as isize, i as isize)
+                .is_some_and(|c| symbols.contains(c))
+            {
                 number.tagged = true;
                 return;
             }
@@ -26,44 +26,49 @@ fn get_surrounding_squares_tag(grid: &[Vec<char>], number: &mut Number) {
     }
 }
 
-pub fn process(_input: &str) -> String {
-    let grid: Vec<Vec<char>> = _input
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect();
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Schematic, AocError> {
+    let grid = Grid::parse(input, |c| c.is_ascii().then_some(c))?;
+    let rows: Vec<&str> = input.lines().collect();
 
-    let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
+    let mut visited = Grid::new(grid.width(), grid.height(), false);
     let mut numbers: Vec<Number> = Vec::new();
 
     // Process grid and mark numbers as visited
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &pixel) in row.iter().enumerate() {
-            if !visited[i][j] && pixel.is_ascii_digit() {
-                // Found the start of a new number
-                let mut num = Vec::new();
-                for jnum in j..grid[0].len() {
-                    if grid[i][jnum].is_ascii_digit() {
-                        num.push(grid[i][jnum]);
-                        visited[i][jnum] = true;
-                    } else {
-                        break;
-                    }
+    for ((j, i), &pixel) in grid.iter() {
+        if !visited[(j, i)] && pixel.is_ascii_digit() {
+            // Found the start of a new number
+            let mut num = Vec::new();
+            for jnum in j..grid.width() {
+                if grid[(jnum, i)].is_ascii_digit() {
+                    num.push(grid[(jnum, i)]);
+                    visited[(jnum, i)] = true;
+                } else {
+                    break;
                 }
-                let found_num: u32 = num
-                    .into_iter()
-                    .collect::<String>()
-                    .parse::<u32>()
-                    .expect("Failed to parse");
-                numbers.push(Number {
-                    number: found_num,
-                    x: j as i32,
-                    y: i as i32,
-                    tagged: false,
-                })
             }
+            let found_num: u32 = number(input, &rows[i][j..j + num.len()])?;
+            numbers.push(Number {
+                number: found_num,
+                x: j as i32,
+                y: i as i32,
+                tagged: false,
+            })
         }
     }
 
+    Ok(Schematic { grid, numbers })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(schematic: Schematic) -> Answer {
+    let Schematic { grid, mut numbers } = schematic;
+
     // Search for tags around each number
     numbers
         .iter_mut()
@@ -74,7 +79,28 @@ pub fn process(_input: &str) -> String {
         .filter(|num| num.tagged)
         .fold(0, |acc, n| acc + n.number);
 
-    output.to_string()
+    output.into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part1::process(_input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 3;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -82,7 +108,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "467..114..\n\
                            ...*......\n\
                            ..35..633.\n\
@@ -93,6 +119,7 @@ mod tests {
                            ......755.\n\
                            ...$.*....\n\
                            .664.598..";
-        assert_eq!("4361", process(input))
+        assert_eq!("4361", process(input)?);
+        Ok(())
     }
 }
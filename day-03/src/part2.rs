@@ -1,3 +1,6 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+use aoc_grid::Grid;
 use std::collections::HashMap;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -19,19 +22,12 @@ struct Number {
     gear: GearOrNot,
 }
 
-fn is_geared(grid: &[Vec<char>], number: &mut Number) {
+fn is_geared(grid: &Grid<char>, number: &mut Number) {
     let size = number.number.to_string().len() as i32;
 
     for i in number.y - 1..=number.y + 1 {
-        if i < 0 || i >= grid.len() as i32 {
-            continue;
-        }
-
         for j in number.x - 1..=number.x + size {
-            if j < 0 || j >= grid[0].len() as i32 {
-                continue;
-            }
-            if grid[i as usize][j as usize] == '*' {
+            if grid.get_signed(j as isize, i as isize) == Some(&'*') {
                 number.gear = GearOrNot::GearLoc(j, i);
                 return;
             }
@@ -39,57 +35,60 @@ fn is_geared(grid: &[Vec<char>], number: &mut Number) {
     }
 }
 
-pub fn process(_input: &str) -> String {
-    let grid: Vec<Vec<char>> = _input
-        .lines()
-        .map(|line| line.trim().chars().collect())
-        .collect();
+pub struct Schematic {
+    grid: Grid<char>,
+    numbers: Vec<Number>,
+}
 
-    let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Schematic, AocError> {
+    let grid = Grid::parse(input, |c| c.is_ascii().then_some(c))?;
+    let rows: Vec<&str> = input.lines().collect();
+
+    let mut visited = Grid::new(grid.width(), grid.height(), false);
     let mut numbers: Vec<Number> = Vec::new();
     let mut gears: Vec<Gear> = Vec::new();
 
     // find gears
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &pixel) in row.iter().enumerate() {
-            if pixel == '*' {
-                gears.push(Gear {
-                    x: j as i32,
-                    y: i as i32,
-                });
-            }
+    for ((j, i), &pixel) in grid.iter() {
+        if pixel == '*' {
+            gears.push(Gear {
+                x: j as i32,
+                y: i as i32,
+            });
         }
     }
 
     // Process grid and mark numbers as visited
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &pixel) in row.iter().enumerate() {
-            if !visited[i][j] && pixel.is_ascii_digit() {
-                // Found the start of a new number
-                let mut num = Vec::new();
-                for jnum in j..grid[0].len() {
-                    if grid[i][jnum].is_ascii_digit() {
-                        num.push(grid[i][jnum]);
-                        visited[i][jnum] = true;
-                    } else {
-                        break;
-                    }
+    for ((j, i), &pixel) in grid.iter() {
+        if !visited[(j, i)] && pixel.is_ascii_digit() {
+            // Found the start of a new number
+            let mut num = Vec::new();
+            for jnum in j..grid.width() {
+                if grid[(jnum, i)].is_ascii_digit() {
+                    num.push(grid[(jnum, i)]);
+                    visited[(jnum, i)] = true;
+                } else {
+                    break;
                 }
-                let found_num: u32 = num
-                    .into_iter()
-                    .collect::<String>()
-                    .parse::<u32>()
-                    .expect("Failed to parse");
-                numbers.push(Number {
-                    number: found_num,
-                    x: j as i32,
-                    y: i as i32,
-                    gear: GearOrNot::None,
-                })
             }
+            let found_num: u32 = number(input, &rows[i][j..j + num.len()])?;
+            numbers.push(Number {
+                number: found_num,
+                x: j as i32,
+                y: i as i32,
+                gear: GearOrNot::None,
+            })
         }
     }
 
+    Ok(Schematic { grid, numbers })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(schematic: Schematic) -> Answer {
+    let Schematic { grid, mut numbers } = schematic;
+
     // Process the numbers to identify gears
     numbers.iter_mut().for_each(|n| is_geared(&grid, n));
 
@@ -118,7 +117,28 @@ pub fn process(_input: &str) -> String {
         .map(|(_, val)| val)
         .fold(0, |acc: u32, v| acc + v.iter().product::<u32>());
 
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part2::process(_input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 3;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +146,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "467..114..\n\
                            ...*......\n\
                            ..35..633.\n\
@@ -137,6 +157,7 @@ mod tests {
                            ......755.\n\
                            ...$.*....\n\
                            .664.598..";
-        assert_eq!("467835", process(input))
+        assert_eq!("467835", process(input)?);
+        Ok(())
     }
 }
@@ -0,0 +1,42 @@
+//! Made-up scratchcards, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "cards";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1_000, 4_000, 16_000, 64_000];
+
+/// `size` cards with 10 winning numbers and 25 picks each.
+///
+/// Most cards win nothing, so the number of copies in part 2 stays bounded however many cards
+/// there are.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let mut numbers: Vec<u32> = (1..100).collect();
+    let cards: Vec<String> = (1..=size)
+        .map(|id| {
+            rng.shuffle(&mut numbers);
+            let matches = match rng.below(20) {
+                0..=11 => 0,
+                12..=16 => 1,
+                17..=18 => 2,
+                _ => rng.range(3..11) as usize,
+            };
+            let winners = &numbers[..10];
+            let mut picks: Vec<u32> = winners[..matches].to_vec();
+            picks.extend(&numbers[10..35 - matches]);
+            rng.shuffle(&mut picks);
+
+            let list = |numbers: &[u32]| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            format!("Card {:>3}: {} | {}", id, list(winners), list(&picks))
+        })
+        .collect();
+    cards.join("\n")
+}
@@ -1,28 +1,68 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
 use std::collections::HashSet;
 
-pub fn process(_input: &str) -> String {
+pub struct Card {
+    winners: HashSet<u32>,
+    picks: HashSet<u32>,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Card>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            let (header, picks) = line
+                .split_once('|')
+                .ok_or_else(|| AocError::at(input, line, "expected `|`"))?;
+            let (_, winners) = header
+                .split_once(':')
+                .ok_or_else(|| AocError::at(input, header, "expected `Card <id>:`"))?;
+            let winners = winners
+                .split_whitespace()
+                .map(|num| number(input, num))
+                .collect::<Result<_, _>>()?;
+            let picks = picks
+                .split_whitespace()
+                .map(|num| number(input, num))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Card { winners, picks })
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(cards: Vec<Card>) -> Answer {
     let mut score = 0;
-    for line in _input.split('\n') {
-        let split: Vec<&str> = line.split('|').collect();
-        let winners: HashSet<u32> = split[0]
-            .split(':')
-            .nth(1)
-            .unwrap()
-            .split_whitespace()
-            .map(|num| num.parse().unwrap())
-            .collect();
-
-        let picks: HashSet<u32> = split[1]
-            .split_whitespace()
-            .map(|num| num.parse().unwrap())
-            .collect();
-
-        let common_count = winners.intersection(&picks).count() as u32;
+    for card in cards {
+        let common_count = card.winners.intersection(&card.picks).count() as u32;
         if common_count > 0 {
             score += 2u32.pow(common_count - 1);
         }
     }
-    score.to_string()
+    score.into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part1::process(_input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 4;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -30,13 +70,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
                      Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
                      Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
                      Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
                      Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
                      Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("13", process(input))
+        assert_eq!("13", process(input)?);
+        Ok(())
     }
 }
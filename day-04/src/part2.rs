@@ -1,29 +1,39 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
 use std::cmp;
 use std::collections::HashSet;
 
-pub fn score_card(line: &str) -> usize {
-    let split: Vec<&str> = line.split('|').collect();
-    let winners: HashSet<u32> = split[0]
-        .split(':')
-        .nth(1)
-        .unwrap()
+pub fn score_card(input: &str, line: &str) -> Result<usize, AocError> {
+    let (header, picks) = line
+        .split_once('|')
+        .ok_or_else(|| AocError::at(input, line, "expected `|`"))?;
+    let (_, winners) = header
+        .split_once(':')
+        .ok_or_else(|| AocError::at(input, header, "expected `Card <id>:`"))?;
+    let winners: HashSet<u32> = winners
         .split_whitespace()
-        .map(|num| num.parse().unwrap())
-        .collect();
-
-    let picks: HashSet<u32> = split[1]
+        .map(|num| number(input, num))
+        .collect::<Result<_, _>>()?;
+    let picks: HashSet<u32> = picks
         .split_whitespace()
-        .map(|num| num.parse().unwrap())
-        .collect();
+        .map(|num| number(input, num))
+        .collect::<Result<_, _>>()?;
+
+    Ok(winners.intersection(&picks).count())
+}
 
-    winners.intersection(&picks).count()
+/// Number of winning picks on each card
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<usize>, AocError> {
+    input.lines().map(|line| score_card(input, line)).collect()
 }
-pub fn process(_input: &str) -> String {
-    let pile_size: usize = _input.lines().count();
+
+#[tracing::instrument(skip_all)]
+pub fn solve(scores: Vec<usize>) -> Answer {
+    let pile_size: usize = scores.len();
     let mut cardpile: Vec<u32> = vec![1; pile_size];
 
-    for (i, line) in _input.lines().enumerate() {
-        let score = score_card(line);
+    for (i, score) in scores.into_iter().enumerate() {
         let top = cmp::min(pile_size, i + score + 1);
         let multiplier = cardpile[i];
 
@@ -35,7 +45,28 @@ pub fn process(_input: &str) -> String {
     }
 
     let output: u32 = cardpile.iter().sum();
-    output.to_string()
+    output.into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part2::process(_input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 4;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -43,25 +74,22 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\n\
                      Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19\n\
                      Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1\n\
                      Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83\n\
                      Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36\n\
                      Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!("30", process(input))
+        assert_eq!("30", process(input)?);
+        Ok(())
     }
 
     #[test]
     fn test_score_card() {
-        assert_eq!(
-            4,
-            score_card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")
-        );
-        assert_eq!(
-            0,
-            score_card("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36")
-        );
+        let card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+        assert_eq!(Ok(4), score_card(card, card));
+        let card = "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36";
+        assert_eq!(Ok(0), score_card(card, card));
     }
 }
@@ -0,0 +1,55 @@
+//! Made-up almanacs, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "ranges per map";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[50, 200, 800, 3_200];
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+const LIMIT: u64 = 1 << 32;
+
+/// Ten seed ranges and seven maps that each shuffle about `size` ranges of `0..2^32`.
+///
+/// Like the real almanacs, each map moves whole ranges around without overlapping them, and a
+/// few ranges are left out so their values map to themselves.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let seeds: Vec<String> = (0..10)
+        .map(|_| {
+            let len = rng.range(1..1 << 28);
+            format!("{} {}", rng.range(0..LIMIT - len), len)
+        })
+        .collect();
+    let mut almanac = format!("seeds: {}", seeds.join(" "));
+
+    for name in MAPS {
+        let mut cuts: Vec<u64> = (0..size.saturating_sub(1))
+            .map(|_| rng.range(1..LIMIT))
+            .collect();
+        cuts.extend([0, LIMIT]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut ranges: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        rng.shuffle(&mut ranges);
+
+        almanac.push_str(&format!("\n\n{} map:", name));
+        let mut dest = 0;
+        for (source, len) in ranges {
+            if rng.below(10) > 0 {
+                almanac.push_str(&format!("\n{} {} {}", dest, source, len));
+            }
+            dest += len;
+        }
+    }
+    almanac
+}
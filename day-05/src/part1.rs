@@ -1,63 +1,53 @@
-struct Range {
-    source_start: u64,
-    source_end: u64,
-    dest_start: u64,
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+use aoc_interval::{Interval, Shift};
+
+fn process_map(map: &[Shift], value: u64) -> u64 {
+    map.iter()
+        .find_map(|shift| shift.apply(value))
+        .unwrap_or(value)
 }
 
-impl Range {
-    fn new(slice: &[u64]) -> Range {
-        if slice.len() < 3 {
-            panic!("need 3 u64's");
-        }
-
-        Range {
-            dest_start: slice[0],
-            source_start: slice[1],
-            source_end: slice[1] + slice[2] - 1,
-        }
-    }
-
-    fn process_val(&self, value: u64) -> Option<u64> {
-        if value >= self.source_start && value <= self.source_end {
-            return Some(self.dest_start + value - self.source_start);
-        }
-        None
-    }
+fn generate_map(input: &str, map_str: &[&str]) -> Result<Vec<Shift>, AocError> {
+    map_str
+        .iter()
+        .skip(1)
+        .map(|line| {
+            let nums: Vec<u64> = line
+                .split_whitespace()
+                .map(|n| number(input, n))
+                .collect::<Result<_, _>>()?;
+            if nums.len() != 3 {
+                return Err(AocError::at(
+                    input,
+                    line,
+                    "expected `<dest start> <source start> <length>`",
+                ));
+            }
+            Interval::from_len(nums[1], nums[2])
+                .and_then(|source| Shift::new(source, nums[0]))
+                .ok_or_else(|| AocError::at(input, line, "range runs past the largest number"))
+        })
+        .collect()
 }
 
-fn process_map(map: &[Range], value: u64) -> u64 {
-    let found = map.iter().find_map(|r| r.process_val(value));
-
-    match found {
-        Some(found) => found,
-        None => value,
-    }
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<Vec<Shift>>,
 }
 
-fn generate_map(map_str: &[&str]) -> Vec<Range> {
-    let mut map: Vec<Range> = Vec::new();
-
-    for line in map_str.iter().skip(1) {
-        let nums: Vec<u64> = line
-            .split_whitespace()
-            .map(|n| n.parse::<u64>().unwrap())
-            .collect();
-
-        map.push(Range::new(&nums));
-    }
-    map
-}
-
-pub fn process(input: &str) -> String {
-    let seeds: Vec<u64> = input
-        .lines()
-        .take(2)
-        .next()
-        .unwrap()
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Almanac, AocError> {
+    let first = input.lines().next().unwrap_or(input);
+    let seeds: Vec<u64> = first
+        .strip_prefix("seeds:")
+        .ok_or_else(|| AocError::at(input, first, "expected `seeds:`"))?
         .split_whitespace()
-        .skip(1)
-        .map(|n| n.parse().unwrap())
-        .collect();
+        .map(|n| number(input, n))
+        .collect::<Result<_, _>>()?;
+    if seeds.is_empty() {
+        return Err(AocError::at(input, first, "no seeds"));
+    }
 
     let lines: Vec<&str> = input.lines().skip(2).collect();
 
@@ -79,19 +69,51 @@ pub fn process(input: &str) -> String {
         maps.push(current_map);
     }
 
-    let generated_maps: Vec<Vec<Range>> = maps.iter().map(|m| generate_map(m)).collect();
+    Ok(Almanac {
+        seeds,
+        maps: maps
+            .iter()
+            .map(|m| generate_map(input, m))
+            .collect::<Result<_, _>>()?,
+    })
+}
 
-    let location: u64 = seeds
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: Almanac) -> Answer {
+    let location: u64 = almanac
+        .seeds
         .iter()
         .map(|&seed| {
-            generated_maps
+            almanac
+                .maps
                 .iter()
                 .fold(seed, |value, map| process_map(map, value))
         })
         .min()
         .unwrap();
 
-    location.to_string()
+    location.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 5;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -99,7 +121,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "seeds: 79 14 55 13\n\
                     \n\
                     seed-to-soil map:\n\
@@ -133,6 +155,7 @@ mod tests {
                     humidity-to-location map:\n\
                     60 56 37\n\
                     56 93 4";
-        assert_eq!("35", process(input))
+        assert_eq!("35", process(input)?);
+        Ok(())
     }
 }
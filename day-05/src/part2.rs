@@ -1,6 +1,132 @@
-pub fn process(_input: &str) -> String {
-    println!("hi part 2");
-    "part 2".to_string()
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+use aoc_interval::{Interval, IntervalSet, Shift};
+
+fn generate_map(input: &str, map_str: &[&str]) -> Result<Vec<Shift>, AocError> {
+    map_str
+        .iter()
+        .skip(1)
+        .map(|line| {
+            let nums: Vec<u64> = line
+                .split_whitespace()
+                .map(|n| number(input, n))
+                .collect::<Result<_, _>>()?;
+            if nums.len() != 3 {
+                return Err(AocError::at(
+                    input,
+                    line,
+                    "expected `<dest start> <source start> <length>`",
+                ));
+            }
+            Interval::from_len(nums[1], nums[2])
+                .and_then(|source| Shift::new(source, nums[0]))
+                .ok_or_else(|| AocError::at(input, line, "range runs past the largest number"))
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: IntervalSet<u64>,
+    maps: Vec<Vec<Shift>>,
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Almanac, AocError> {
+    let first = input.lines().next().unwrap_or(input);
+    let tokens: Vec<&str> = first
+        .strip_prefix("seeds:")
+        .ok_or_else(|| AocError::at(input, first, "expected `seeds:`"))?
+        .split_whitespace()
+        .collect();
+    let numbers: Vec<u64> = tokens
+        .iter()
+        .map(|n| number(input, n))
+        .collect::<Result<_, _>>()?;
+    if numbers.is_empty() {
+        return Err(AocError::at(input, first, "no seeds"));
+    }
+    if !numbers.len().is_multiple_of(2) {
+        return Err(AocError::at(
+            input,
+            first,
+            "expected seeds as `<start> <length>` pairs",
+        ));
+    }
+    // An empty range would leave no seed to find a location for
+    if let Some(i) = (1..numbers.len()).step_by(2).find(|&i| numbers[i] == 0) {
+        return Err(AocError::at(input, tokens[i], "empty seed range"));
+    }
+    let seeds = (0..numbers.len())
+        .step_by(2)
+        .map(|i| {
+            Interval::from_len(numbers[i], numbers[i + 1]).ok_or_else(|| {
+                AocError::at(input, tokens[i + 1], "range runs past the largest number")
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let lines: Vec<&str> = input.lines().skip(2).collect();
+
+    let mut maps: Vec<Vec<&str>> = Vec::new();
+    let mut current_map: Vec<&str> = Vec::new();
+
+    for item in lines {
+        if item.is_empty() {
+            if !current_map.is_empty() {
+                maps.push(current_map);
+                current_map = Vec::new();
+            }
+        } else {
+            current_map.push(item);
+        }
+    }
+
+    if !current_map.is_empty() {
+        maps.push(current_map);
+    }
+
+    Ok(Almanac {
+        seeds,
+        maps: maps
+            .iter()
+            .map(|m| generate_map(input, m))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: Almanac) -> Answer {
+    let locations = almanac
+        .maps
+        .iter()
+        .fold(almanac.seeds, |values, map| values.apply_shifts(map));
+
+    locations
+        .min()
+        .expect("parse rejects empty seed ranges")
+        .into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 5;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -8,8 +134,64 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let input = "";
-        assert_eq!("", process(input))
+    fn test_process() -> Result<(), AocError> {
+        let input = "seeds: 79 14 55 13\n\
+                    \n\
+                    seed-to-soil map:\n\
+                    50 98 2\n\
+                    52 50 48\n\
+                    \n\
+                    soil-to-fertilizer map:\n\
+                    0 15 37\n\
+                    37 52 2\n\
+                    39 0 15\n\
+                    \n\
+                    fertilizer-to-water map:\n\
+                    49 53 8\n\
+                    0 11 42\n\
+                    42 0 7\n\
+                    57 7 4\n\
+                    \n\
+                    water-to-light map:\n\
+                    88 18 7\n\
+                    18 25 70\n\
+                    \n\
+                    light-to-temperature map:\n\
+                    45 77 23\n\
+                    81 45 19\n\
+                    68 64 13\n\
+                    \n\
+                    temperature-to-humidity map:\n\
+                    0 69 1\n\
+                    1 0 69\n\
+                    \n\
+                    humidity-to-location map:\n\
+                    60 56 37\n\
+                    56 93 4";
+        assert_eq!("46", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_odd_seeds() {
+        let error = parse("seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+
+    #[test]
+    fn test_empty_seed_range() {
+        let error = parse("seeds: 1 0\n\nseed-to-soil map:\n1 1 1").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        assert_eq!(error.message, "empty seed range");
+    }
+
+    #[test]
+    fn test_range_overflow() {
+        let error = parse("seeds: 18446744073709551615 2\n\nseed-to-soil map:\n1 1 1").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 29));
+
+        let error = parse("seeds: 1 2\n\nseed-to-soil map:\n18446744073709551615 1 2").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.message, "range runs past the largest number");
     }
 }
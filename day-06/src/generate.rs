@@ -0,0 +1,29 @@
+//! Made-up race sheets, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "races";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1, 2, 3, 4];
+
+/// `size` races, at most four so that part 2's single long race still fits in a `u64`.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let races: Vec<(u64, u64)> = (0..size.clamp(1, 4))
+        .map(|_| {
+            let time = rng.range(10..100);
+            (time, rng.range(1..time * time / 4))
+        })
+        .collect();
+
+    let row = |label: &str, values: Vec<u64>| {
+        let values: Vec<String> = values.iter().map(|v| format!("{:>5}", v)).collect();
+        format!("{:<9}{}", label, values.join(" "))
+    };
+    format!(
+        "{}\n{}",
+        row("Time:", races.iter().map(|r| r.0).collect()),
+        row("Distance:", races.iter().map(|r| r.1).collect())
+    )
+}
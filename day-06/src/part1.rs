@@ -1,38 +1,72 @@
-fn generate_races(input: &str) -> Vec<(u32, u32)> {
-    let times: Vec<u32> = input
-        .lines()
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
+use aoc_common::parse::{self, labelled, numbers};
+use aoc_common::{Answer, AocError, Solution};
+use aoc_math::quadratic_below_zero;
+use nom::character::complete::line_ending;
+use nom::combinator::consumed;
+use nom::sequence::separated_pair;
 
-    let distances: Vec<u32> = input
-        .lines()
-        .last()
-        .unwrap()
-        .split_whitespace()
-        .skip(1)
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect();
+#[tracing::instrument(skip_all)]
+fn generate_races(input: &str) -> Result<Vec<(u32, u32)>, AocError> {
+    let (times, (distance_line, distances)) = parse::finish(
+        input,
+        separated_pair(
+            labelled("Time", numbers),
+            line_ending,
+            consumed(labelled("Distance", numbers)),
+        ),
+    )?;
 
-    assert_eq!(times.len(), distances.len());
+    if times.len() != distances.len() {
+        return Err(AocError::at(
+            input,
+            distance_line,
+            format!("expected {} distances", times.len()),
+        ));
+    }
     let output: Vec<_> = times
         .iter()
         .copied()
         .zip(distances.iter().copied())
         .collect();
-    output
+    Ok(output)
+}
+
+/// How many whole-millisecond button holds beat `record` in a race lasting `time`.
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    // Holding for t travels t * (time - t), which wins when t^2 - time * t + record < 0
+    quadratic_below_zero(1, -(time as i128), record as i128)
+        .map_or(0, |holds| (holds.end() - holds.start() + 1) as u64)
 }
-pub fn process(input: &str) -> String {
-    let races = generate_races(input);
+
+#[tracing::instrument(skip_all)]
+pub fn solve(races: Vec<(u32, u32)>) -> Answer {
     let output: u32 = races
         .iter()
-        .map(|race| (1..race.0).filter(|&i| i * (race.0 - i) > race.1).count() as u32)
+        .map(|&(time, record)| ways_to_win(time as u64, record as u64) as u32)
         .product();
 
-    output.to_string()
+    output.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 6;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<(u32, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        generate_races(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -40,19 +74,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_generate_races() {
+    fn test_generate_races() -> Result<(), AocError> {
         let input = "Time:      7  15   30\n\
                      Distance:  9  40  200";
-        let races = generate_races(input);
+        let races = generate_races(input)?;
         assert_eq!(races.first(), Some(&(7, 9)));
         assert_eq!(races.get(1), Some(&(15, 40)));
         assert_eq!(races.get(2), Some(&(30, 200)));
         assert_eq!(races.get(3), None);
+        Ok(())
     }
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "Time:      7  15   30\n\
                      Distance:  9  40  200";
-        assert_eq!("288", process(input))
+        assert_eq!("288", process(input)?);
+        Ok(())
     }
 }
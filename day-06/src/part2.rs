@@ -1,30 +1,68 @@
-fn generate_race(input: &str) -> (u64, u64) {
-    let time_str: String = input
-        .lines()
-        .next()
-        .unwrap()
-        .chars()
-        .skip(6)
-        .filter(|c| !c.is_whitespace())
-        .collect();
-
-    let distance_str: String = input
-        .lines()
-        .last()
-        .unwrap()
-        .chars()
-        .skip(10)
-        .filter(|c| !c.is_whitespace())
-        .collect();
-
-    (time_str.parse().unwrap(), distance_str.parse().unwrap())
+use aoc_common::parse::{self, labelled, IResult};
+use aoc_common::{Answer, AocError, Solution};
+use aoc_math::quadratic_below_zero;
+use nom::character::complete::{digit1, line_ending, space1};
+use nom::combinator::{map_res, recognize};
+use nom::error::context;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+/// A single number, ignoring the spaces between its digits.
+fn kerned(input: &str) -> IResult<'_, u64> {
+    context(
+        "a number",
+        map_res(
+            recognize(separated_list1(space1, digit1)),
+            |digits: &str| digits.replace(' ', "").parse(),
+        ),
+    )(input)
+}
+
+#[tracing::instrument(skip_all)]
+fn generate_race(input: &str) -> Result<(u64, u64), AocError> {
+    parse::finish(
+        input,
+        separated_pair(
+            labelled("Time", kerned),
+            line_ending,
+            labelled("Distance", kerned),
+        ),
+    )
+}
+
+/// How many whole-millisecond button holds beat `record` in a race lasting `time`.
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    // Holding for t travels t * (time - t), which wins when t^2 - time * t + record < 0
+    quadratic_below_zero(1, -(time as i128), record as i128)
+        .map_or(0, |holds| (holds.end() - holds.start() + 1) as u64)
 }
-pub fn process(input: &str) -> String {
-    let race = generate_race(input);
 
-    let output = (1..race.0).filter(|&i| i * (race.0 - i) > race.1).count() as u64;
+#[tracing::instrument(skip_all)]
+pub fn solve(race: (u64, u64)) -> Answer {
+    let output = ways_to_win(race.0, race.1);
+
+    output.into()
+}
 
-    output.to_string()
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 6;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = (u64, u64);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        generate_race(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -32,16 +70,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_generate_races() {
+    fn test_generate_races() -> Result<(), AocError> {
         let input = "Time:      7  15   30\n\
                      Distance:  9  40  200";
-        let race = generate_race(input);
+        let race = generate_race(input)?;
         assert_eq!(race, (71530, 940200));
+        Ok(())
     }
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "Time:      7  15   30\n\
                      Distance:  9  40  200";
-        assert_eq!("71503", process(input))
+        assert_eq!("71503", process(input)?);
+        Ok(())
     }
 }
@@ -0,0 +1,25 @@
+//! Made-up Camel Cards hands, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "hands";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1_000, 2_000, 4_000, 8_000];
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// `size` random hands.
+///
+/// Bids stay below 100 so the total winnings still fit in the `u32` the solutions add them up
+/// in, up to about 9000 hands.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let hands: Vec<String> = (0..size)
+        .map(|_| {
+            let hand: String = (0..5).map(|_| char::from(*rng.pick(CARDS))).collect();
+            format!("{} {}", hand, rng.range(1..100))
+        })
+        .collect();
+    hands.join("\n")
+}
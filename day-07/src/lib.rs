@@ -1,2 +1,7 @@
+/// The puzzle's title on adventofcode.com.
+pub const TITLE: &str = "Camel Cards";
+
+pub mod generate;
 pub mod part1;
+pub mod part1_b;
 pub mod part2;
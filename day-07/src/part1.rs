@@ -1,3 +1,5 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug, Ord, PartialEq, PartialOrd, Eq)]
@@ -69,7 +71,7 @@ impl CardValue {
 }
 
 #[derive(Debug, Eq)]
-struct Hand {
+pub struct Hand {
     hand_type: HandType,
     bid: u32,
     score: u32,
@@ -103,11 +105,32 @@ fn score_hand(hand_str: &str) -> u32 {
         .fold(0u32, |acc, card_value| (acc << 4) | card_value)
 }
 
-pub fn process(input: &str) -> String {
+/// Splits a `<hand> <bid>` line, rejecting anything but five known cards.
+fn split_hand<'a>(input: &str, line: &'a str) -> Result<(&'a str, u32), AocError> {
+    let (hand_str, bid_str) = line
+        .split_once(' ')
+        .ok_or_else(|| AocError::at(input, line, "expected `<hand> <bid>`"))?;
+    if let Some((i, c)) = hand_str
+        .char_indices()
+        .find(|&(_, c)| CardValue::from_char(c).is_none())
+    {
+        return Err(AocError::at(
+            input,
+            &hand_str[i..i + c.len_utf8()],
+            "unknown card",
+        ));
+    }
+    if hand_str.len() != 5 {
+        return Err(AocError::at(input, hand_str, "expected five cards"));
+    }
+    Ok((hand_str, number(input, bid_str)?))
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Hand>, AocError> {
     let mut hands: Vec<Hand> = Vec::new();
     for line in input.lines() {
-        let (hand_str, bid_str) = line.split_once(' ').unwrap();
-        let bid: u32 = bid_str.parse().unwrap();
+        let (hand_str, bid) = split_hand(input, line)?;
         let hand_type = HandType::from_str(hand_str);
         let hand = Hand {
             hand_type,
@@ -116,13 +139,38 @@ pub fn process(input: &str) -> String {
         };
         hands.push(hand);
     }
+    Ok(hands)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(mut hands: Vec<Hand>) -> Answer {
     hands.sort();
 
-    let output: u32 = hands
-        .iter()
-        .enumerate()
-        .fold(0u32, |acc, (i, hand)| acc + hand.bid * (i as u32 + 1));
-    output.to_string()
+    let output: u64 = hands.iter().enumerate().fold(0u64, |acc, (i, hand)| {
+        acc + u64::from(hand.bid) * (i as u64 + 1)
+    });
+    output.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 7;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -130,13 +178,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "32T3K 765\n\
                      T55J5 684\n\
                      KK677 28\n\
                      KTJJT 220\n\
                      QQQJA 483";
-        assert_eq!("6440", process(input))
+        assert_eq!("6440", process(input)?);
+        Ok(())
     }
 
     #[test]
@@ -221,4 +270,11 @@ mod tests {
         assert_eq!(v.get(5), Some(&HandType::FourOfAKind));
         assert_eq!(v.get(6), Some(&HandType::FiveOfAKind));
     }
+
+    #[test]
+    fn test_large_bids() -> Result<(), AocError> {
+        let input = "32T3K 4294967295\nKK677 4294967295";
+        assert_eq!("12884901885", process(input)?);
+        Ok(())
+    }
 }
@@ -0,0 +1,141 @@
+use std::ops::Deref;
+
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy)]
+enum HandType {
+    FiveOfAKind = 6,
+    FourOfAKind = 5,
+    FullHouse = 4,
+    ThreeOfAKind = 3,
+    TwoPair = 2,
+    OnePair = 1,
+    HighCard = 0,
+}
+
+fn card_value(card: char) -> Option<u32> {
+    match card {
+        'A' => Some(14),
+        'K' => Some(13),
+        'Q' => Some(12),
+        'J' => Some(11),
+        'T' => Some(10),
+        value => value.to_digit(10),
+    }
+}
+
+fn score_hand(hand: &str) -> (HandType, (u32, u32, u32, u32, u32)) {
+    use HandType::*;
+
+    let counts = hand.chars().counts();
+    let values = counts.values().sorted().join("");
+    let hand_type = match values.deref() {
+        "5" => FiveOfAKind,
+        "14" => FourOfAKind,
+        "23" => FullHouse,
+        "113" => ThreeOfAKind,
+        "122" => TwoPair,
+        "1112" => OnePair,
+        "11111" => HighCard,
+        value => panic!("should never happen. Encountered `{}`", value),
+    };
+    let card_scores = hand
+        .chars()
+        .map(|card| card_value(card).unwrap())
+        .collect_tuple()
+        .unwrap();
+    (hand_type, card_scores)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<(&str, u32)>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line
+                .split_once(' ')
+                .ok_or_else(|| AocError::at(input, line, "expected `<hand> <bid>`"))?;
+            if let Some((i, c)) = hand.char_indices().find(|&(_, c)| card_value(c).is_none()) {
+                return Err(AocError::at(
+                    input,
+                    &hand[i..i + c.len_utf8()],
+                    "unknown card",
+                ));
+            }
+            if hand.len() != 5 {
+                return Err(AocError::at(input, hand, "expected five cards"));
+            }
+            Ok((hand, number(input, bid)?))
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(hands: Vec<(&str, u32)>) -> Answer {
+    let hands = hands
+        .into_iter()
+        .map(|(hand, bid)| (hand, bid, score_hand(hand)))
+        .sorted_by_key(|x| (x.2 .0 as u8, x.2 .1))
+        .enumerate()
+        .map(|(index, (_hand, bid, _))| (index as u64 + 1) * u64::from(bid))
+        .sum::<u64>();
+    hands.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 7;
+    const PART: u8 = 1;
+    const VARIANT: &'static str = "itertools";
+
+    type Parsed<'a> = Vec<(&'a str, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() -> Result<(), AocError> {
+        let input = "32T3K 765\n\
+                     T55J5 684\n\
+                     KK677 28\n\
+                     KTJJT 220\n\
+                     QQQJA 483";
+        assert_eq!("6440", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("32T3K 765\nT55X5 684").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.message.as_str()),
+            (2, 4, "unknown card")
+        );
+        let error = parse("32T3 765").unwrap_err();
+        assert_eq!(error.message, "expected five cards");
+    }
+
+    #[test]
+    fn test_large_bids() -> Result<(), AocError> {
+        let input = "32T3K 4294967295\nKK677 4294967295";
+        assert_eq!("12884901885", process(input)?);
+        Ok(())
+    }
+}
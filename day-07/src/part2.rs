@@ -1,3 +1,5 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
 use core::fmt;
 use std::collections::HashMap;
 
@@ -80,7 +82,7 @@ impl CardValue {
 }
 
 #[derive(Eq)]
-struct Hand {
+pub struct Hand {
     hand_type: HandType,
     bid: u32,
     score: u32,
@@ -125,26 +127,72 @@ fn score_hand(hand_str: &str) -> u32 {
         .fold(0u32, |acc, card_value| (acc << 4) | card_value)
 }
 
-pub fn process(input: &str) -> String {
-    let mut hands: Vec<Hand> = input
+/// Splits a `<hand> <bid>` line, rejecting anything but five known cards.
+fn split_hand<'a>(input: &str, line: &'a str) -> Result<(&'a str, u32), AocError> {
+    let (hand_str, bid_str) = line
+        .split_once(' ')
+        .ok_or_else(|| AocError::at(input, line, "expected `<hand> <bid>`"))?;
+    if let Some((i, c)) = hand_str
+        .char_indices()
+        .find(|&(_, c)| CardValue::from_char(c).is_none())
+    {
+        return Err(AocError::at(
+            input,
+            &hand_str[i..i + c.len_utf8()],
+            "unknown card",
+        ));
+    }
+    if hand_str.len() != 5 {
+        return Err(AocError::at(input, hand_str, "expected five cards"));
+    }
+    Ok((hand_str, number(input, bid_str)?))
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Hand>, AocError> {
+    input
         .lines()
         .map(|line| {
-            let (hand_str, bid_str) = line.split_once(' ').unwrap();
-            Hand {
+            let (hand_str, bid) = split_hand(input, line)?;
+            Ok(Hand {
                 hand_type: HandType::from_str(hand_str),
-                bid: bid_str.parse().unwrap(),
+                bid,
                 score: score_hand(hand_str),
                 string: hand_str.to_string(),
-            }
+            })
         })
-        .collect();
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(mut hands: Vec<Hand>) -> Answer {
     hands.sort();
 
-    let output: u32 = hands
-        .iter()
-        .enumerate()
-        .fold(0u32, |acc, (i, hand)| acc + hand.bid * (i as u32 + 1));
-    output.to_string()
+    let output: u64 = hands.iter().enumerate().fold(0u64, |acc, (i, hand)| {
+        acc + u64::from(hand.bid) * (i as u64 + 1)
+    });
+    output.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 7;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<Hand>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -152,13 +200,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "32T3K 765\n\
                            T55J5 684\n\
                            KK677 28\n\
                            KTJJT 220\n\
                            QQQJA 483";
-        assert_eq!("5905", process(input))
+        assert_eq!("5905", process(input)?);
+        Ok(())
     }
 
     #[test]
@@ -0,0 +1,58 @@
+//! Made-up desert maps, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "nodes";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[500, 1_000, 2_000, 4_000, 8_000];
+
+/// Letters for node names that mustn't end in `A` or `Z`.
+const NAME: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY0123456789";
+const GHOSTS: usize = 6;
+
+/// About `size` nodes, at most 24,000, split into six loops of random length.
+///
+/// Each loop runs from a node ending in `A` to one ending in `Z` and back round, and both
+/// choices at every node lead on to the next, so the instructions never matter. The first loop
+/// goes from `AAA` to `ZZZ` for part 1.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let instructions: String = (0..rng.range(20..300))
+        .map(|_| if rng.chance(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let mut names = (0..NAME.len().pow(3)).map(|i| {
+        let letter = |n: usize| char::from(NAME[n % NAME.len()]);
+        format!(
+            "{}{}{}",
+            letter(i / NAME.len().pow(2)),
+            letter(i / NAME.len()),
+            letter(i)
+        )
+    });
+    let size = size.clamp(GHOSTS * 2, 24_000);
+    let mut lines = Vec::with_capacity(size);
+    for (ghost, &prefix) in NAME[..GHOSTS].iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => {
+                let prefix = char::from(prefix);
+                (
+                    format!("{}{}A", prefix, prefix),
+                    format!("{}{}Z", prefix, prefix),
+                )
+            }
+        };
+        let len = size / GHOSTS / 2 + rng.below(size / GHOSTS);
+        let mut ring = vec![start];
+        ring.extend(names.by_ref().take(len.max(2) - 2));
+        ring.push(end);
+        for (i, node) in ring.iter().enumerate() {
+            let next = &ring[(i + 1) % ring.len()];
+            lines.push(format!("{} = ({}, {})", node, next, next));
+        }
+    }
+    rng.shuffle(&mut lines);
+    format!("{}\n\n{}", instructions, lines.join("\n"))
+}
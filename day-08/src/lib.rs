@@ -1,2 +1,7 @@
+/// The puzzle's title on adventofcode.com.
+pub const TITLE: &str = "Haunted Wasteland";
+
+pub mod generate;
 pub mod part1;
 pub mod part2;
+pub mod route;
@@ -1,6 +1,15 @@
-use core::panic;
+use aoc_common::parse::{self, key_pair, lines, word};
+use aoc_common::{Answer, AocError, Solution};
+use aoc_graph::flood_fill;
+use nom::bytes::complete::is_a;
+use nom::character::complete::line_ending;
+use nom::combinator::peek;
+use nom::error::context;
+use nom::sequence::{pair, separated_pair, terminated};
 use std::fmt;
 
+use crate::route::Route;
+
 #[derive(Debug, Default)]
 struct ArenaTree {
     arena: Vec<Node>,
@@ -42,36 +51,120 @@ impl ArenaTree {
         idx
     }
 }
-pub fn process(input: &str) -> String {
-    let instructions: Vec<_> = input.lines().next().unwrap().chars().collect();
+#[derive(Debug)]
+pub struct Network {
+    instructions: Vec<char>,
+    tree: ArenaTree,
+}
 
-    let mut tree: ArenaTree = ArenaTree::default();
-    input.lines().skip(2).for_each(|line| {
-        let name = &line[0..3];
-        let left = &line[7..10];
-        let right = &line[12..15];
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Network, AocError> {
+    let (instructions, nodes) = parse::finish(
+        input,
+        separated_pair(
+            context(
+                "`L`/`R` instructions",
+                terminated(is_a("LR"), peek(line_ending)),
+            ),
+            pair(line_ending, line_ending),
+            lines(key_pair(word, word, word)),
+        ),
+    )?;
+    let instructions: Vec<_> = instructions.chars().collect();
 
+    let mut tree: ArenaTree = ArenaTree::default();
+    for &(name, (left, right)) in &nodes {
         let node = tree.node(name);
         tree.arena[node].right = Some(tree.node(right));
         tree.arena[node].left = Some(tree.node(left));
-    });
+    }
 
-    let mut node = tree.node("AAA");
-    let target_node = tree.node("ZZZ");
-    let mut step_count = 0;
-    let mut next_instruction = instructions.iter().cycle();
-    while node != target_node {
-        step_count += 1;
-        let left = tree.arena[node].left.unwrap();
-        let right = tree.arena[node].right.unwrap();
-        match next_instruction.next().unwrap() {
-            'L' => node = left,
-            'R' => node = right,
-            _ => panic!("unexpected character in instructions"),
+    if let Some(node) = tree.arena.iter().find(|node| node.left.is_none()) {
+        let offset = input.find(node.name.as_str()).unwrap_or(0);
+        return Err(AocError::at_offset(
+            input,
+            offset,
+            &node.name,
+            "node is never defined",
+        ));
+    }
+    for name in ["AAA", "ZZZ"] {
+        if !tree.arena.iter().any(|node| node.name == name) {
+            return Err(AocError::at(
+                input,
+                &input[input.len()..],
+                format!("no node `{}`", name),
+            ));
         }
     }
 
-    step_count.to_string()
+    let start = tree.node("AAA");
+    let target = tree.node("ZZZ");
+    let reachable = flood_fill(start, |&node| {
+        let node = &tree.arena[node];
+        [node.left.unwrap(), node.right.unwrap()]
+    });
+    if !reachable.contains(&target) {
+        let (name, _) = nodes.iter().find(|(name, _)| *name == "AAA").unwrap();
+        return Err(AocError::at(
+            input,
+            name,
+            "`ZZZ` can't be reached from here",
+        ));
+    }
+
+    Ok(Network { instructions, tree })
+}
+
+impl Network {
+    /// One step from `node`, about to follow instruction `i`.
+    fn step(&self, (node, i): (usize, usize)) -> (usize, usize) {
+        let next = &self.tree.arena[node];
+        let node = match self.instructions[i] {
+            'L' => next.left.unwrap(),
+            _ => next.right.unwrap(),
+        };
+        (node, (i + 1) % self.instructions.len())
+    }
+}
+
+/// Steps from `AAA` to `ZZZ`, or why it never gets there.
+#[tracing::instrument(skip_all)]
+pub fn solve(mut network: Network) -> Answer {
+    let start = network.tree.node("AAA");
+    let target = network.tree.node("ZZZ");
+    // The instructions can keep us off `ZZZ` even though it can be reached, so stop once we're
+    // back where we've been
+    let route = Route::walk(
+        (start, 0),
+        |state| network.step(state),
+        |(node, _)| node == target,
+    );
+    match route.first() {
+        Some(steps) => steps.into(),
+        None => "no answer: the instructions never lead to `ZZZ`".into(),
+    }
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 8;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Network;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -79,7 +172,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "RL\n\
                      \n\
                      AAA = (BBB, CCC)\n\
@@ -89,6 +182,31 @@ mod tests {
                      EEE = (EEE, EEE)\n\
                      GGG = (GGG, GGG)\n\
                      ZZZ = (ZZZ, ZZZ)";
-        assert_eq!("2", process(input))
+        assert_eq!("2", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse("LRX\n\nAAA = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(error.message, "expected `L`/`R` instructions");
+
+        let error = parse("LR\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ ZZZ)").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 12));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let error = parse("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (AAA, ZZZ)").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.message, "`ZZZ` can't be reached from here");
+    }
+
+    #[test]
+    fn test_instructions_avoid_zzz() -> Result<(), AocError> {
+        let input = "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        assert!(!process(input)?.is_number());
+        Ok(())
     }
 }
@@ -1,6 +1,183 @@
-pub fn process(_input: &str) -> String {
-    println!("hi part 2");
-    "part 2".to_string()
+use aoc_common::parse::{self, key_pair, lines, word};
+use aoc_common::{Answer, AocError, Solution};
+use aoc_graph::flood_fill;
+use nom::bytes::complete::is_a;
+use nom::character::complete::line_ending;
+use nom::combinator::peek;
+use nom::error::context;
+use nom::sequence::{pair, separated_pair, terminated};
+use std::fmt;
+
+use crate::route::{first_meeting, Route};
+
+#[derive(Debug, Default)]
+struct ArenaTree {
+    arena: Vec<Node>,
+}
+
+struct Node {
+    idx: usize,
+    name: String,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl Node {
+    fn new(idx: usize, name: &str) -> Self {
+        Self {
+            idx,
+            name: name.to_string(),
+            left: None,
+            right: None,
+        }
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.idx)
+    }
+}
+
+impl ArenaTree {
+    fn node(&mut self, name: &str) -> usize {
+        for node in &self.arena {
+            if node.name == name {
+                return node.idx;
+            }
+        }
+        let idx = self.arena.len();
+        self.arena.push(Node::new(idx, name));
+        idx
+    }
+}
+pub struct Network {
+    instructions: Vec<char>,
+    tree: ArenaTree,
+    /// Every node ending in `A`.
+    starts: Vec<usize>,
+}
+
+impl Network {
+    /// One step of a ghost standing on `node`, about to follow instruction `i`.
+    fn step(&self, (node, i): (usize, usize)) -> (usize, usize) {
+        let next = &self.tree.arena[node];
+        let node = match self.instructions[i] {
+            'L' => next.left.unwrap(),
+            _ => next.right.unwrap(),
+        };
+        (node, (i + 1) % self.instructions.len())
+    }
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Network, AocError> {
+    let (instructions, nodes) = parse::finish(
+        input,
+        separated_pair(
+            context(
+                "`L`/`R` instructions",
+                terminated(is_a("LR"), peek(line_ending)),
+            ),
+            pair(line_ending, line_ending),
+            lines(key_pair(word, word, word)),
+        ),
+    )?;
+    let instructions: Vec<_> = instructions.chars().collect();
+
+    let mut tree: ArenaTree = ArenaTree::default();
+    for &(name, (left, right)) in &nodes {
+        let node = tree.node(name);
+        tree.arena[node].right = Some(tree.node(right));
+        tree.arena[node].left = Some(tree.node(left));
+    }
+
+    if let Some(node) = tree.arena.iter().find(|node| node.left.is_none()) {
+        let offset = input.find(node.name.as_str()).unwrap_or(0);
+        return Err(AocError::at_offset(
+            input,
+            offset,
+            &node.name,
+            "node is never defined",
+        ));
+    }
+    for suffix in ['A', 'Z'] {
+        if !tree.arena.iter().any(|node| node.name.ends_with(suffix)) {
+            return Err(AocError::at(
+                input,
+                &input[input.len()..],
+                format!("no node ending in `{}`", suffix),
+            ));
+        }
+    }
+
+    let mut starts = Vec::new();
+    for &(name, _) in nodes.iter().filter(|(name, _)| name.ends_with('A')) {
+        let start = tree.node(name);
+        // Cheaper than walking the ghost, though it may still miss `Z` by following the instructions
+        let reachable = flood_fill(start, |&node| {
+            let node = &tree.arena[node];
+            [node.left.unwrap(), node.right.unwrap()]
+        });
+        if !reachable
+            .iter()
+            .any(|&node| tree.arena[node].name.ends_with('Z'))
+        {
+            return Err(AocError::at(
+                input,
+                name,
+                "no node ending in `Z` can be reached from here",
+            ));
+        }
+        starts.push(start);
+    }
+
+    Ok(Network {
+        instructions,
+        tree,
+        starts,
+    })
+}
+
+/// The first step every ghost is on a `Z` node, or why there isn't one.
+#[tracing::instrument(skip_all)]
+pub fn solve(network: Network) -> Answer {
+    let routes: Vec<Route> = network
+        .starts
+        .iter()
+        .map(|&start| {
+            Route::walk(
+                (start, 0),
+                |state| network.step(state),
+                |(node, _)| network.tree.arena[node].name.ends_with('Z'),
+            )
+        })
+        .collect();
+    match first_meeting(&routes) {
+        Some(steps) => steps.into(),
+        None => "no answer: the ghosts are never all on `Z` nodes at once".into(),
+    }
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 8;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Network;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -8,8 +185,76 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let input = "";
-        assert_eq!("", process(input))
+    fn test_process() -> Result<(), AocError> {
+        let input = "LR\n\
+                     \n\
+                     11A = (11B, XXX)\n\
+                     11B = (XXX, 11Z)\n\
+                     11Z = (11B, XXX)\n\
+                     22A = (22B, XXX)\n\
+                     22B = (22C, 22C)\n\
+                     22C = (22Z, 22Z)\n\
+                     22Z = (22B, 22B)\n\
+                     XXX = (XXX, XXX)";
+        assert_eq!("6", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_meeting_in_tail() -> Result<(), AocError> {
+        // the only ghost passes a Z node once, then stays on 11B for ever
+        let input = "L\n\
+                     \n\
+                     11A = (11Z, 11Z)\n\
+                     11Z = (11B, 11B)\n\
+                     11B = (11B, 11B)\n\
+                     XXX = (XXX, XXX)";
+        assert_eq!("1", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_meeting() -> Result<(), AocError> {
+        let input = "L\n\
+                     \n\
+                     11A = (11B, 11B)\n\
+                     11B = (11B, 11B)\n\
+                     11Z = (11Z, 11Z)";
+        let error = process(input).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.message,
+            "no node ending in `Z` can be reached from here"
+        );
+
+        // one ghost is on Z at odd steps, the other at even ones
+        let input = "L\n\
+                     \n\
+                     11A = (11Z, 11Z)\n\
+                     11Z = (11A, 11A)\n\
+                     22A = (22B, 22B)\n\
+                     22B = (22Z, 22Z)\n\
+                     22Z = (22B, 22B)";
+        assert!(!process(input)?.is_number());
+        Ok(())
+    }
+
+    #[test]
+    fn test_offset_cycles() -> Result<(), AocError> {
+        // one ghost reaches Z after 1 step then every 2, the other after 4 steps then every 3
+        let input = "L\n\
+                     \n\
+                     11A = (11Z, 11Z)\n\
+                     11Z = (11B, 11B)\n\
+                     11B = (11Z, 11Z)\n\
+                     22A = (22B, 22B)\n\
+                     22B = (22C, 22C)\n\
+                     22C = (22D, 22D)\n\
+                     22D = (22Z, 22Z)\n\
+                     22Z = (22E, 22E)\n\
+                     22E = (22F, 22F)\n\
+                     22F = (22Z, 22Z)";
+        assert_eq!("7", process(input)?);
+        Ok(())
     }
 }
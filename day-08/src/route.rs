@@ -0,0 +1,189 @@
+//! Where a ghost walking the network stands at every step, without walking it for ever.
+
+use aoc_common::cycle;
+use aoc_math::crt;
+
+/// The steps at which one ghost stands on a node it's looking for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// Steps on a target before the ghost's loop starts, in order.
+    tail: Vec<u64>,
+    /// The first step that is part of the loop.
+    loop_start: u64,
+    /// Steps on a target in the first time round the loop, in order.
+    loop_hits: Vec<u64>,
+    /// How many steps until `loop_hits` come round again.
+    period: u64,
+}
+
+impl Route {
+    /// Walks a ghost from `start` until its state repeats, which it must since there are only so
+    /// many nodes and places in the instructions.
+    pub fn walk<S: Copy + Eq>(
+        start: S,
+        step: impl Fn(S) -> S,
+        is_target: impl Fn(S) -> bool,
+    ) -> Route {
+        let cycle = cycle::brent(start, |&state| step(state));
+        let mut hits = Vec::new();
+        let mut state = start;
+        for steps in 0..(cycle.start + cycle.length) as u64 {
+            if is_target(state) {
+                hits.push(steps);
+            }
+            state = step(state);
+        }
+        let loop_start = cycle.start as u64;
+        let loop_hits = hits.split_off(hits.partition_point(|&hit| hit < loop_start));
+        Route {
+            tail: hits,
+            loop_start,
+            loop_hits,
+            period: cycle.length as u64,
+        }
+        .shortest_period()
+    }
+
+    /// Shrinks the loop to the shortest period its hits repeat at.
+    ///
+    /// A ghost whose path ignores the instructions loops through the same nodes many times before
+    /// its place in the instructions comes round too, which would otherwise multiply the hits.
+    fn shortest_period(self) -> Route {
+        let offsets: Vec<u64> = self
+            .loop_hits
+            .iter()
+            .map(|hit| hit - self.loop_start)
+            .collect();
+        let period = (1..self.period)
+            .filter(|&p| self.period.is_multiple_of(p))
+            .find(|p| {
+                offsets
+                    .iter()
+                    .all(|offset| offsets.binary_search(&((offset + p) % self.period)).is_ok())
+            })
+            .unwrap_or(self.period);
+        let end = self.loop_start + period;
+        Route {
+            loop_hits: self
+                .loop_hits
+                .into_iter()
+                .filter(|&hit| hit < end)
+                .collect(),
+            period,
+            ..self
+        }
+    }
+
+    /// The first step the ghost is on a target, if it ever is.
+    pub fn first(&self) -> Option<u64> {
+        self.tail.first().or(self.loop_hits.first()).copied()
+    }
+
+    fn on_target(&self, step: u64) -> bool {
+        if step < self.loop_start {
+            return self.tail.binary_search(&step).is_ok();
+        }
+        let offset = (step - self.loop_start) % self.period;
+        self.loop_hits
+            .iter()
+            .any(|hit| hit - self.loop_start == offset)
+    }
+}
+
+/// The first step at which every ghost is on a target, if there is one.
+pub fn first_meeting(routes: &[Route]) -> Option<u128> {
+    let latest = routes.iter().max_by_key(|route| route.loop_start)?;
+    // Until the last ghost starts looping, it can only be on a target in its tail
+    if let Some(&step) = latest
+        .tail
+        .iter()
+        .find(|&&step| routes.iter().all(|route| route.on_target(step)))
+    {
+        return Some(step as u128);
+    }
+
+    // After that every ghost is looping, so on a target at `hit + k * period` for any of its
+    // loop hits. Solve those together for every combination of hits
+    let mut meetings = vec![(0, 1)];
+    for route in routes {
+        meetings = meetings
+            .iter()
+            .flat_map(|&meeting| {
+                route
+                    .loop_hits
+                    .iter()
+                    .filter_map(move |&hit| crt([meeting, (hit as u128, route.period as u128)]))
+            })
+            .collect();
+        meetings.sort_unstable();
+        meetings.dedup();
+    }
+    let latest = latest.loop_start as u128;
+    meetings
+        .into_iter()
+        .map(|(steps, period)| {
+            if steps < latest {
+                steps + (latest - steps).div_ceil(period) * period
+            } else {
+                steps
+            }
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk() {
+        // a ring of four nodes, entered from node 0, with the target at node 1
+        let route = Route::walk(
+            0,
+            |node| if node == 3 { 1 } else { node + 1 },
+            |node| node == 1,
+        );
+        assert_eq!(
+            route,
+            Route {
+                tail: vec![],
+                loop_start: 1,
+                loop_hits: vec![1],
+                period: 3,
+            }
+        );
+        assert_eq!(route.first(), Some(1));
+        assert!(route.on_target(7));
+        assert!(!route.on_target(8));
+    }
+
+    #[test]
+    fn test_shortest_period() {
+        // a ring of three nodes walked with two instructions only repeats its state every 6
+        let route = Route {
+            tail: vec![],
+            loop_start: 0,
+            loop_hits: vec![2, 5],
+            period: 6,
+        };
+        assert_eq!(route.shortest_period().period, 3);
+    }
+
+    #[test]
+    fn test_first_meeting() {
+        let route = |tail: Vec<u64>, loop_start, loop_hits: Vec<u64>, period| Route {
+            tail,
+            loop_start,
+            loop_hits,
+            period,
+        };
+        // on a target at 1 only, then stuck
+        assert_eq!(first_meeting(&[route(vec![1], 2, vec![], 1)]), Some(1));
+        // at 1, 3, 5... and at 4, 7, 10...
+        let routes = [route(vec![], 1, vec![1], 2), route(vec![], 2, vec![4], 3)];
+        assert_eq!(first_meeting(&routes), Some(7));
+        // odd and even steps
+        let routes = [route(vec![], 0, vec![1], 2), route(vec![], 0, vec![2], 2)];
+        assert_eq!(first_meeting(&routes), None);
+    }
+}
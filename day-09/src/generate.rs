@@ -0,0 +1,28 @@
+//! Made-up OASIS reports, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "histories";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[1_000, 4_000, 16_000, 64_000];
+
+/// `size` histories of 21 values, each following a polynomial of degree at most six.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let lines: Vec<String> = (0..size)
+        .map(|_| {
+            let degree = rng.range(1..7) as usize;
+            let coefficients: Vec<i64> =
+                (0..=degree).map(|_| rng.range(0..11) as i64 - 5).collect();
+            let values: Vec<String> = (0..21i64)
+                .map(|x| {
+                    let value = coefficients.iter().rev().fold(0, |acc, &c| acc * x + c);
+                    value.to_string()
+                })
+                .collect();
+            values.join(" ")
+        })
+        .collect();
+    lines.join("\n")
+}
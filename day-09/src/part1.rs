@@ -1,5 +1,8 @@
-fn process_line(sequence: &[i64]) -> i64 {
-    let mut diffs: Vec<Vec<i64>> = vec![sequence.to_vec()];
+use aoc_common::parse::{self, lines, numbers};
+use aoc_common::{Answer, AocError, Solution};
+
+fn process_line(sequence: &[i64]) -> i128 {
+    let mut diffs: Vec<Vec<i128>> = vec![sequence.iter().map(|&x| x.into()).collect()];
     loop {
         diffs.push(
             diffs
@@ -7,7 +10,7 @@ fn process_line(sequence: &[i64]) -> i64 {
                 .unwrap()
                 .windows(2)
                 .map(|w| w[1] - w[0])
-                .collect::<Vec<i64>>(),
+                .collect::<Vec<i128>>(),
         );
 
         if diffs.last().unwrap().iter().all(|x| *x == 0) {
@@ -22,21 +25,54 @@ fn process_line(sequence: &[i64]) -> i64 {
         .fold(0, |acc, row| acc + row.last().unwrap())
 }
 
-pub fn process(input: &str) -> String {
-    let lines: Vec<Vec<i64>> = input
-        .lines()
-        .map(|s| {
-            s.split_whitespace()
-                .filter_map(|num| num.parse().ok())
-                .collect()
-        })
-        .collect();
+/// Parses the histories, rejecting any whose extrapolation could overflow.
+///
+/// Each row of differences is at most twice as big as the one above it, so a history of `n`
+/// values no bigger than `m` extrapolates to less than `m * 2^n`.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    let histories: Vec<Vec<i64>> = parse::finish(input, lines(numbers))?;
+    let mut total: u128 = 0;
+    for (line, history) in input.lines().zip(&histories) {
+        let largest = history.iter().map(|x| x.unsigned_abs()).max().unwrap_or(0);
+        total = 1u128
+            .checked_shl(history.len() as u32)
+            .and_then(|scale| scale.checked_mul(largest.into()))
+            .and_then(|bound| total.checked_add(bound))
+            .filter(|&total| total <= i128::MAX as u128)
+            .ok_or_else(|| AocError::at(input, line, "values too large to extrapolate"))?;
+    }
+    Ok(histories)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: Vec<Vec<i64>>) -> Answer {
     let sum = lines
         .iter()
-        .fold(0i64, |acc, line| acc + process_line(line));
+        .fold(0i128, |acc, line| acc + process_line(line));
+
+    sum.into()
+}
 
-    sum.to_string()
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 9;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -44,10 +80,27 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "0 3 6 9 12 15\n\
                      1 3 6 10 15 21\n\
                      10 13 16 21 30 45";
-        assert_eq!("114", process(input))
+        assert_eq!("114", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_near_i64_limits() -> Result<(), AocError> {
+        let input = "9223372036854775807 -9223372036854775808 9223372036854775807";
+        assert_eq!("64563604257983430652", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_too_large() {
+        let history = vec![i64::MAX.to_string(); 70].join(" ");
+        let input = format!("1 2 3\n{}", history);
+        let error = parse(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "values too large to extrapolate");
     }
 }
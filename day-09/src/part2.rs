@@ -1,5 +1,7 @@
-fn process_line(sequence: &[i64]) -> i64 {
-    let mut diffs: Vec<Vec<i64>> = vec![sequence.to_vec()];
+use aoc_common::{Answer, AocError, Solution};
+
+fn process_line(sequence: &[i64]) -> i128 {
+    let mut diffs: Vec<Vec<i128>> = vec![sequence.iter().map(|&x| x.into()).collect()];
     loop {
         diffs.push(
             diffs
@@ -7,7 +9,7 @@ fn process_line(sequence: &[i64]) -> i64 {
                 .unwrap()
                 .windows(2)
                 .map(|w| w[1] - w[0])
-                .collect::<Vec<i64>>(),
+                .collect::<Vec<i128>>(),
         );
 
         if diffs.last().unwrap().iter().all(|x| *x == 0) {
@@ -22,21 +24,40 @@ fn process_line(sequence: &[i64]) -> i64 {
         .fold(0, |acc, row| row.first().unwrap() - acc)
 }
 
-pub fn process(input: &str) -> String {
-    let lines: Vec<Vec<i64>> = input
-        .lines()
-        .map(|s| {
-            s.split_whitespace()
-                .filter_map(|num| num.parse().ok())
-                .collect()
-        })
-        .collect();
+/// Parses the histories as [part 1](crate::part1::parse) does.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    crate::part1::parse(input)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: Vec<Vec<i64>>) -> Answer {
     let sum = lines
         .iter()
-        .fold(0i64, |acc, line| acc + process_line(line));
+        .fold(0i128, |acc, line| acc + process_line(line));
+
+    sum.into()
+}
 
-    sum.to_string()
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 9;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -44,10 +65,18 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "0 3 6 9 12 15\n\
                      1 3 6 10 15 21\n\
                      10 13 16 21 30 45";
-        assert_eq!("2", process(input))
+        assert_eq!("2", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_near_i64_limits() -> Result<(), AocError> {
+        let input = "9223372036854775807 -9223372036854775808 9223372036854775807";
+        assert_eq!("64563604257983430652", process(input)?);
+        Ok(())
     }
 }
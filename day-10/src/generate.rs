@@ -0,0 +1,72 @@
+//! Made-up pipe mazes, for timing how the solutions scale.
+
+use std::cmp::Ordering;
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "grid side";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[70, 140, 280, 560];
+
+/// A grid about `size` on a side filled by one loop of pipe, starting from `S` in the corner.
+///
+/// The loop snakes back and forth along the rows and comes home up the first column, so it
+/// passes through every tile. The seed only decides whether the maze is mirrored left to right.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let width = size.max(3);
+    // An even number of rows brings the snake back next to the first column
+    let height = size.max(2).div_ceil(2) * 2;
+
+    let mut path = Vec::with_capacity(width * height);
+    path.extend((0..width).map(|x| (x, 0)));
+    for y in 1..height {
+        if y % 2 == 1 {
+            path.extend((1..width).rev().map(|x| (x, y)));
+        } else {
+            path.extend((1..width).map(|x| (x, y)));
+        }
+    }
+    path.extend((1..height).rev().map(|y| (0, y)));
+
+    let mut grid = vec![vec!['.'; width]; height];
+    for (i, &(x, y)) in path.iter().enumerate() {
+        let prev = path[(i + path.len() - 1) % path.len()];
+        let next = path[(i + 1) % path.len()];
+        let towards = |(px, py): (usize, usize)| match (px.cmp(&x), py.cmp(&y)) {
+            (_, Ordering::Less) => 'N',
+            (_, Ordering::Greater) => 'S',
+            (Ordering::Less, _) => 'W',
+            _ => 'E',
+        };
+        let mut ends = [towards(prev), towards(next)];
+        ends.sort_unstable();
+        grid[y][x] = match ends {
+            ['N', 'S'] => '|',
+            ['E', 'W'] => '-',
+            ['E', 'N'] => 'L',
+            ['N', 'W'] => 'J',
+            ['S', 'W'] => '7',
+            _ => 'F',
+        };
+    }
+    grid[0][0] = 'S';
+
+    if rng.chance(0.5) {
+        for row in &mut grid {
+            row.reverse();
+            for tile in row.iter_mut() {
+                *tile = match *tile {
+                    'L' => 'J',
+                    'J' => 'L',
+                    '7' => 'F',
+                    'F' => '7',
+                    other => other,
+                };
+            }
+        }
+    }
+    let rows: Vec<String> = grid.into_iter().map(String::from_iter).collect();
+    rows.join("\n")
+}
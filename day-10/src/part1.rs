@@ -1,64 +1,75 @@
-use std::collections::HashMap;
-use std::collections::VecDeque;
-
-fn find_start(map: &[Vec<char>]) -> Option<(usize, usize)> {
-    for (i, row) in map.iter().enumerate() {
-        if let Some(j) = row.iter().position(|&c| c == 'S') {
-            return Some((i, j));
-        }
+use aoc_common::{Answer, AocError, Solution};
+use aoc_geometry::{Direction, Point};
+use aoc_graph::bfs;
+use aoc_grid::Grid;
+
+fn find_start(map: &Grid<char>) -> Option<(usize, usize)> {
+    map.find(|&c| c == 'S').map(|(x, y)| (y, x))
+}
+
+/// Pipes that connect back to a tile when they sit one step away in `direction`.
+fn connecting_pipes(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "|7F",
+        Direction::Right => "-7J",
+        Direction::Down => "|LJ",
+        Direction::Left => "-FL",
     }
-    None
 }
 
-fn get_touching_pipes(map: &[Vec<char>], y: usize, x: usize) -> Vec<(usize, usize)> {
-    let mut smap: HashMap<(i32, i32), &str> = HashMap::new();
-    smap.insert((-1, 0), "|7F"); // Up one
-    smap.insert((0, 1), "-7J"); // Right one
-    smap.insert((0, -1), "-FL"); // Left one
-    smap.insert((1, 0), "|LJ"); // Down one
-
-    smap.iter()
-        .filter_map(|(&k, &v)| {
-            let (yd, xd) = (y as i32 + k.0, x as i32 + k.1);
-            if yd >= 0
-                && yd < map.len() as i32
-                && xd >= 0
-                && xd < map[0].len() as i32
-                && v.contains(map[yd as usize][xd as usize])
-            {
-                Some((yd as usize, xd as usize))
-            } else {
-                None
-            }
+fn get_touching_pipes(map: &Grid<char>, y: usize, x: usize) -> Vec<(usize, usize)> {
+    let here = Point::from((x, y));
+    Direction::ALL
+        .into_iter()
+        .filter_map(|direction| {
+            let next = here.step(direction);
+            map.get_signed(next.x as isize, next.y as isize)
+                .filter(|&&c| connecting_pipes(direction).contains(c))
+                .map(|_| (next.y as usize, next.x as usize))
         })
         .collect()
 }
 
-pub fn process(input: &str) -> String {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Grid<char>, AocError> {
+    let map = Grid::parse(input, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    if find_start(&map).is_none() {
+        return Err(AocError::at(
+            input,
+            &input[input.len()..],
+            "no start tile `S`",
+        ));
+    }
+    Ok(map)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(map: Grid<char>) -> Answer {
     let start = find_start(&map).unwrap();
-    let mut dists = vec![vec![None; map[0].len()]; map.len()];
-
-    let mut stack: VecDeque<(usize, usize, i32)> = VecDeque::new();
-    stack.push_back((start.0, start.1, 0));
-    let mut peak_distance = 0;
-
-    while let Some((y, x, dist)) = stack.pop_front() {
-        if dists[y][x].is_some() {
-            continue;
-        }
-        if dist > peak_distance {
-            peak_distance = dist;
-        }
-
-        dists[y][x] = Some(dist);
-        let next_points = get_touching_pipes(&map, y, x);
-        // println!("({}, {}) -> {:?}", y, x, next_points);
-        next_points
-            .iter()
-            .for_each(|&p| stack.push_back((p.0, p.1, dist + 1)));
+    let distances = bfs(start, |&(y, x)| get_touching_pipes(&map, y, x));
+    let peak_distance = distances.values().max().unwrap();
+    (*peak_distance).into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 10;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
     }
-    peak_distance.to_string()
 }
 
 #[cfg(test)]
@@ -67,12 +78,14 @@ mod tests {
 
     #[test]
     fn test_get_touching_pipes() {
-        let input = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '7', 'x', 'x', 'x'],
-            vec!['|', 'S', '-', 'x', 'x'],
-            vec!['.', 'F', '.', '.', '.'],
-        ];
+        let input = Grid::parse(
+            ".....\n\
+             .7xxx\n\
+             |S-xx\n\
+             .F...",
+            Some,
+        )
+        .unwrap();
         let output = get_touching_pipes(&input, 2, 1);
         assert!(output.contains(&(1, 1)));
         assert!(output.contains(&(2, 2)));
@@ -83,30 +96,35 @@ mod tests {
 
     #[test]
     fn test_find_start() {
-        let input = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '7', 'x', 'x', 'x'],
-            vec!['.', 'S', 'x', 'x', 'x'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
+        let input = Grid::parse(
+            ".....\n\
+             .7xxx\n\
+             .Sxxx\n\
+             .....",
+            Some,
+        )
+        .unwrap();
         assert_eq!(Some((2, 1)), find_start(&input));
 
-        let input = vec![
-            vec!['.', '.', '.', '.', '.'],
-            vec!['.', '7', 'x', 'x', 'x'],
-            vec!['.', 'x', 'x', 'x', 'x'],
-            vec!['.', '.', '.', '.', '.'],
-        ];
+        let input = Grid::parse(
+            ".....\n\
+             .7xxx\n\
+             .xxxx\n\
+             .....",
+            Some,
+        )
+        .unwrap();
         assert!(find_start(&input).is_none());
     }
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = ".....\n\
                      .S-7.\n\
                      .|.|.\n\
                      .L-J.\n\
                      .....";
-        assert_eq!("4", process(input))
+        assert_eq!("4", process(input)?);
+        Ok(())
     }
 }
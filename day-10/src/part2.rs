@@ -1,6 +1,34 @@
-pub fn process(_input: &str) -> String {
-    println!("hi part 2");
-    "part 2".to_string()
+use aoc_common::{Answer, AocError, Solution};
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<&str, AocError> {
+    Ok(input)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(_input: &str) -> Answer {
+    "part 2".into()
+}
+
+pub fn process(_input: &str) -> Result<Answer, AocError> {
+    Part2::process(_input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 10;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -8,8 +36,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    #[ignore = "part 2 not solved yet"]
+    fn test_process() -> Result<(), AocError> {
         let input = "";
-        assert_eq!("", process(input))
+        assert_eq!("", process(input)?);
+        Ok(())
     }
 }
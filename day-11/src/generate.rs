@@ -0,0 +1,39 @@
+//! Made-up telescope images, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "grid side";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[35, 70, 140, 280];
+
+/// A `size` by `size` image with a galaxy in about one tile in fifty.
+///
+/// About one row and column in ten is left empty, so the universe has room to expand.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2);
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.chance(0.1)).collect();
+
+    let mut grid: Vec<Vec<char>> = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let open = !empty_rows[y] && !empty_columns[x];
+                    if open && rng.chance(0.02) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    // Always at least one pair to measure
+    grid[0][0] = '#';
+    grid[size - 1][size - 1] = '#';
+
+    let rows: Vec<String> = grid.into_iter().map(String::from_iter).collect();
+    rows.join("\n")
+}
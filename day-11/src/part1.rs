@@ -1,99 +1,98 @@
+use aoc_common::{Answer, AocError, Solution};
+use aoc_geometry::Point;
+use aoc_grid::Grid;
 use itertools::Itertools;
 
-fn manhatten_distance(y1: i32, x1: i32, y2: i32, x2: i32) -> i32 {
-    (x1 - x2).abs() + (y1 - y2).abs()
+fn get_galaxies(universe: &Grid<bool>) -> Vec<Point> {
+    universe
+        .iter()
+        .filter(|(_, &g)| g)
+        .map(|(position, _)| Point::from(position))
+        .collect()
 }
 
-fn get_galaxies(universe: &[Vec<bool>]) -> Vec<(usize, usize)> {
-    let mut galaxies = Vec::new();
+fn expand_universe(universe: &Grid<bool>) -> Grid<bool> {
+    let blank_rows: Vec<usize> = (0..universe.height())
+        .filter(|&y| universe.row(y).iter().all(|&g| !g))
+        .collect();
+    let blank_cols: Vec<usize> = (0..universe.width())
+        .filter(|&x| universe.column(x).all(|&g| !g))
+        .collect();
 
-    for (i, row) in universe.iter().enumerate() {
-        row.iter().enumerate().for_each(|(j, &g)| {
-            if g {
-                galaxies.push((i, j));
-            }
-        });
+    // Every blank row or column before a galaxy pushes it one further away
+    let mut expanded = Grid::new(
+        universe.width() + blank_cols.len(),
+        universe.height() + blank_rows.len(),
+        false,
+    );
+    for galaxy in get_galaxies(universe) {
+        let (x, y) = (galaxy.x as usize, galaxy.y as usize);
+        let y = y + blank_rows.iter().filter(|&&row| row < y).count();
+        let x = x + blank_cols.iter().filter(|&&col| col < x).count();
+        expanded[(x, y)] = true;
     }
-
-    galaxies
+    expanded
 }
 
-fn expand_universe(universe: &mut Vec<Vec<bool>>) {
-    // Add blank rows
-    let mut new_universe = Vec::new();
-    let blank_row = vec![false; universe[0].len()];
-    for row in universe.iter() {
-        if row.iter().all(|&g| !g) {
-            new_universe.push(blank_row.clone());
-        }
-        new_universe.push(row.clone());
-    }
-    *universe = new_universe;
-
-    // Add blank columns
-    let mut cols = Vec::new();
-
-    for col in 0..universe[0].len() {
-        let this_col: Vec<_> = universe
-            .iter()
-            .filter_map(|row| row.get(col))
-            .copied()
-            .collect();
-        if this_col.iter().all(|&g| !g) {
-            cols.push(col);
-        }
-    }
-    cols.sort_by(|a, b| b.cmp(a));
-
-    for row in universe.iter_mut() {
-        for col in &cols {
-            row.insert(*col, false);
-        }
-    }
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Grid<bool>, AocError> {
+    Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
 }
 
-pub fn process(input: &str) -> String {
-    let mut universe: Vec<Vec<bool>> = input
-        .lines()
-        .map(|line| line.chars().map(|c| c == '#').collect())
-        .collect();
-
-    expand_universe(&mut universe);
-    let galaxies = get_galaxies(&universe);
+#[tracing::instrument(skip_all)]
+pub fn solve(universe: Grid<bool>) -> Answer {
+    let galaxies = get_galaxies(&expand_universe(&universe));
     let pairs = galaxies.into_iter().combinations(2).collect_vec();
 
     let sum = pairs.iter().fold(0, |acc, v| {
         let a = v.first().unwrap();
         let b = v.last().unwrap();
-        acc + manhatten_distance(a.0 as i32, a.1 as i32, b.0 as i32, b.1 as i32)
+        acc + a.manhattan(*b)
     });
 
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 11;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Grid<bool>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_manhatten_distance() {
-        assert_eq!(9, manhatten_distance(6, 1, 11, 5));
-        assert_eq!(15, manhatten_distance(0, 4, 10, 9));
-        assert_eq!(17, manhatten_distance(2, 0, 7, 12));
-        assert_eq!(5, manhatten_distance(11, 0, 11, 5));
-    }
-
     #[test]
     fn test_get_galaxies() {
-        let universe = vec![vec![false, true, false], vec![false, true, true]];
+        let universe = Grid::from_vec(3, vec![false, true, false, false, true, true]);
         let galaxies = get_galaxies(&universe);
-        assert!(galaxies.contains(&(0, 1)));
-        assert!(galaxies.contains(&(1, 1)));
-        assert!(galaxies.contains(&(1, 2)));
+        assert!(galaxies.contains(&Point::new(1, 0)));
+        assert!(galaxies.contains(&Point::new(1, 1)));
+        assert!(galaxies.contains(&Point::new(2, 1)));
     }
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "...#......\n\
                      .......#..\n\
                      #.........\n\
@@ -104,6 +103,7 @@ mod tests {
                      ..........\n\
                      .......#..\n\
                      #...#.....";
-        assert_eq!("374", process(input))
+        assert_eq!("374", process(input)?);
+        Ok(())
     }
 }
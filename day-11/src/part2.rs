@@ -1,3 +1,6 @@
+use aoc_common::{Answer, AocError, Solution};
+use aoc_geometry::Point;
+use aoc_grid::Grid;
 use itertools::Itertools;
 
 const EMPTY: u32 = 0;
@@ -5,101 +8,68 @@ const GALAXY: u32 = 1;
 const ROW_GAP: u32 = 2;
 const COL_GAP: u32 = 4;
 
-fn manhatten_distance(
-    universe: &[Vec<u32>],
-    gap_size: i64,
-    y1: i64,
-    x1: i64,
-    y2: i64,
-    x2: i64,
-) -> i64 {
-    let x_0 = std::cmp::min(x1, x2) as usize;
-    let x_1 = std::cmp::max(x1, x2) as usize;
-    let y_0 = std::cmp::min(y1, y2) as usize;
-    let y_1 = std::cmp::max(y1, y2) as usize;
+/// Distance between two galaxies once every gap has grown to `gap_size`.
+fn expanded_distance(universe: &Grid<u32>, gap_size: i64, a: Point, b: Point) -> i64 {
+    let x_0 = a.x.min(b.x) as usize;
+    let x_1 = a.x.max(b.x) as usize;
+    let y_0 = a.y.min(b.y) as usize;
+    let y_1 = a.y.max(b.y) as usize;
 
     let row_gaps = universe
-        .iter()
+        .column(0)
         .skip(y_0)
         .take(y_1 - y_0)
-        .map(|row| row.first().unwrap())
         .filter(|&e| e & ROW_GAP != 0)
         .count() as i64;
 
     let col_gaps = universe
-        .first()
-        .unwrap()
+        .row(0)
         .iter()
         .skip(x_0)
         .take(x_1 - x_0)
         .filter(|&e| e & COL_GAP != 0)
         .count() as i64;
 
-    (x1 - x2).abs() + (y1 - y2).abs() + row_gaps * (gap_size - 1) + col_gaps * (gap_size - 1)
+    a.manhattan(b) + row_gaps * (gap_size - 1) + col_gaps * (gap_size - 1)
 }
 
-fn get_galaxies(universe: &[Vec<u32>]) -> Vec<(usize, usize)> {
-    let mut galaxies = Vec::new();
-
-    for (i, row) in universe.iter().enumerate() {
-        row.iter().enumerate().for_each(|(j, &g)| {
-            if g == GALAXY {
-                galaxies.push((i, j));
-            }
-        });
-    }
-
-    galaxies
+fn get_galaxies(universe: &Grid<u32>) -> Vec<Point> {
+    universe
+        .iter()
+        .filter(|(_, &g)| g == GALAXY)
+        .map(|(position, _)| Point::from(position))
+        .collect()
 }
 
-fn expand_universe(universe: &mut Vec<Vec<u32>>) {
-    // Add blank rows
-    let mut new_universe = Vec::new();
-    let row_gap = vec![ROW_GAP; universe[0].len()];
-    for row in universe.iter() {
-        if !row.iter().any(|&g| g == GALAXY) {
-            new_universe.push(row_gap.clone());
-        } else {
-            new_universe.push(row.clone());
-        }
-    }
+fn expand_universe(universe: &mut Grid<u32>) {
+    let blank_rows: Vec<usize> = (0..universe.height())
+        .filter(|&y| !universe.row(y).contains(&GALAXY))
+        .collect();
+    let blank_cols: Vec<usize> = (0..universe.width())
+        .filter(|&x| !universe.column(x).any(|&g| g == GALAXY))
+        .collect();
 
-    // Add blank columns
-    let mut cols = Vec::new();
-
-    for col in 0..universe[0].len() {
-        let this_col: Vec<_> = universe
-            .iter()
-            .filter_map(|row| row.get(col))
-            .copied()
-            .collect();
-        if !this_col.iter().any(|&g| g == GALAXY) {
-            cols.push(col);
-        }
+    for y in blank_rows {
+        universe.row_mut(y).fill(ROW_GAP);
     }
-    cols.sort_by(|a, b| b.cmp(a));
-
-    for row in new_universe.iter_mut() {
-        for col in &cols {
-            row[*col] |= COL_GAP;
+    for x in blank_cols {
+        for y in 0..universe.height() {
+            universe[(x, y)] |= COL_GAP;
         }
     }
-    *universe = new_universe;
 }
 
-pub fn process(input: &str, gap_size: i64) -> String {
-    let mut universe: Vec<Vec<u32>> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => GALAXY,
-                    _ => EMPTY,
-                })
-                .collect()
-        })
-        .collect();
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Grid<u32>, AocError> {
+    Grid::parse(input, |c| match c {
+        '#' => Some(GALAXY),
+        '.' => Some(EMPTY),
+        _ => None,
+    })
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(mut universe: Grid<u32>, gap_size: i64) -> Answer {
     expand_universe(&mut universe);
     let galaxies = get_galaxies(&universe);
     let pairs = galaxies.into_iter().combinations(2).collect_vec();
@@ -107,12 +77,34 @@ pub fn process(input: &str, gap_size: i64) -> String {
     let sum: i64 = pairs.iter().fold(0, |acc, v| {
         let a = v.first().unwrap();
         let b = v.last().unwrap();
-        acc + manhatten_distance(
-            &universe, gap_size, a.0 as i64, a.1 as i64, b.0 as i64, b.1 as i64,
-        )
+        acc + expanded_distance(&universe, gap_size, *a, *b)
     });
 
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    <Part2>::process(input)
+}
+
+/// Part 2, with each empty row or column growing to `GAP` of them. The puzzle's own examples
+/// use smaller gaps than its real answer, so the gap is part of the type rather than an
+/// argument the runner would have no way to pass.
+pub struct Part2<const GAP: i64 = 1_000_000>;
+
+impl<const GAP: i64> Solution for Part2<GAP> {
+    const DAY: u8 = 11;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed, GAP)
+    }
 }
 
 #[cfg(test)]
@@ -121,15 +113,15 @@ mod tests {
 
     #[test]
     fn test_get_galaxies() {
-        let universe = vec![vec![EMPTY, GALAXY, EMPTY], vec![EMPTY, GALAXY, GALAXY]];
+        let universe = Grid::from_vec(3, vec![EMPTY, GALAXY, EMPTY, EMPTY, GALAXY, GALAXY]);
         let galaxies = get_galaxies(&universe);
-        assert!(galaxies.contains(&(0, 1)));
-        assert!(galaxies.contains(&(1, 1)));
-        assert!(galaxies.contains(&(1, 2)));
+        assert!(galaxies.contains(&Point::new(1, 0)));
+        assert!(galaxies.contains(&Point::new(1, 1)));
+        assert!(galaxies.contains(&Point::new(2, 1)));
     }
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "...#......\n\
                      .......#..\n\
                      #.........\n\
@@ -140,7 +132,8 @@ mod tests {
                      ..........\n\
                      .......#..\n\
                      #...#.....";
-        assert_eq!("8410", process(input, 100));
-        assert_eq!("1030", process(input, 10));
+        assert_eq!("8410", Part2::<100>::process(input)?);
+        assert_eq!("1030", Part2::<10>::process(input)?);
+        Ok(())
     }
 }
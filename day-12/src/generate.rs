@@ -0,0 +1,39 @@
+//! Made-up spring condition records, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "springs per row";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[20, 40, 80, 160, 320];
+
+/// 200 rows of `size` springs, each with at most ten unknowns.
+///
+/// Capping the unknowns keeps part 2's arrangement counts, which grow with every unknown in
+/// the unfolded row, inside a `usize`.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    let rows: Vec<String> = (0..200)
+        .map(|_| {
+            let mut springs: Vec<char> = (0..size)
+                .map(|_| if rng.chance(0.4) { '#' } else { '.' })
+                .collect();
+            // Every row needs at least one damaged group
+            let forced = rng.below(size);
+            springs[forced] = '#';
+
+            let groups: Vec<String> = springs
+                .split(|&c| c == '.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect();
+            for _ in 0..(size / 3).min(10) {
+                let i = rng.below(size);
+                springs[i] = '?';
+            }
+            format!("{} {}", String::from_iter(springs), groups.join(","))
+        })
+        .collect();
+    rows.join("\n")
+}
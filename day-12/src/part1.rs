@@ -1,3 +1,6 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Status {
     Operational,
@@ -16,7 +19,7 @@ impl Status {
 }
 
 #[derive(Debug)]
-struct Group {
+pub struct Group {
     springs: Vec<Status>,
     brokens: Vec<u32>,
 }
@@ -94,13 +97,65 @@ fn count_arrangements(group: Group) -> usize {
     cache[group.springs.len()][group.brokens.len()]
 }
 
-pub fn process(input: &str) -> String {
+/// Rejects anything `Group::from_string` would misread.
+fn check_line(input: &str, line: &str) -> Result<(), AocError> {
+    let (springs, brokens) = line
+        .split_once(' ')
+        .ok_or_else(|| AocError::at(input, line, "expected `<springs> <group sizes>`"))?;
+    if let Some((i, c)) = springs.char_indices().find(|&(_, c)| !".#?".contains(c)) {
+        return Err(AocError::at(
+            input,
+            &springs[i..i + c.len_utf8()],
+            "unknown spring",
+        ));
+    }
+    for size in brokens.split(',') {
+        if number::<u32>(input, size)? == 0 {
+            return Err(AocError::at(input, size, "group sizes must be positive"));
+        }
+    }
+    Ok(())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Group>, AocError> {
+    input
+        .lines()
+        .map(|line| {
+            check_line(input, line)?;
+            Ok(Group::from_string(line))
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(groups: Vec<Group>) -> Answer {
     let mut sum = 0;
-    for line in input.lines() {
-        let g = Group::from_string(line);
+    for g in groups {
         sum += count_arrangements(g);
     }
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 12;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -112,7 +167,7 @@ mod tests {
         let input = "???.### 1,1,3";
         let g = Group::from_string(input);
         assert_eq!(g.springs.len(), 7);
-        assert_eq!(g.springs.get(0).unwrap(), &Status::Unknown);
+        assert_eq!(g.springs.first().unwrap(), &Status::Unknown);
         assert_eq!(g.springs.get(1).unwrap(), &Status::Unknown);
         assert_eq!(g.springs.get(2).unwrap(), &Status::Unknown);
         assert_eq!(g.springs.get(3).unwrap(), &Status::Operational);
@@ -124,14 +179,15 @@ mod tests {
     }
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "???.### 1,1,3
 .??..??...?##. 1,1,3
 ?#?#?#?#?#?#?#? 1,3,1,6
 ????.#...#... 4,1,1
 ????.######..#####. 1,6,5
 ?###???????? 3,2,1";
-        assert_eq!("21", process(input))
+        assert_eq!("21", process(input)?);
+        Ok(())
     }
 
     #[test]
@@ -0,0 +1,92 @@
+//! Made-up patterns of ash and rocks, for timing how the solutions scale.
+
+use std::collections::HashMap;
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "patterns";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[100, 200, 400, 800];
+
+/// Lines of reflection in `rows`, as the number of rows above each.
+fn reflections(rows: &[Vec<bool>]) -> Vec<usize> {
+    (1..rows.len())
+        .filter(|&line| {
+            let span = line.min(rows.len() - line);
+            (0..span).all(|i| rows[line - 1 - i] == rows[line + i])
+        })
+        .collect()
+}
+
+fn transpose(rows: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..rows[0].len())
+        .map(|x| rows.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// One pattern with a single horizontal line of reflection and a smudge hiding a vertical one,
+/// or `None` if chance gave it any other lines.
+///
+/// The clean pattern is symmetric both ways. The horizontal line sits in the top half so the
+/// bottom rows reflect in nothing, and flipping a tile there breaks only the vertical symmetry.
+fn pattern(rng: &mut Rng) -> Option<Vec<Vec<bool>>> {
+    let (width, height) = (rng.range(5..18) as usize, rng.range(5..18) as usize);
+    let across = rng.range(1..(height as u64 - 1) / 2 + 1) as usize;
+    let down = rng.range(1..width as u64) as usize;
+    let fold = |i: usize, line: usize, len: usize| {
+        let span = line.min(len - line);
+        match i {
+            i if i + span < line || i >= line + span => i,
+            i if i < line => i,
+            i => 2 * line - 1 - i,
+        }
+    };
+
+    let mut tiles = HashMap::new();
+    let mut rows: Vec<Vec<bool>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    let key = (fold(x, down, width), fold(y, across, height));
+                    *tiles.entry(key).or_insert_with(|| rng.chance(0.5))
+                })
+                .collect()
+        })
+        .collect();
+
+    let span = down.min(width - down);
+    let x = down - span + rng.below(2 * span);
+    let y = rng.range(2 * across as u64..height as u64) as usize;
+    rows[y][x] = !rows[y][x];
+    let reflects_once = reflections(&rows) == [across] && reflections(&transpose(&rows)).is_empty();
+    // Cleaning the smudge should bring back the vertical line and nothing else
+    rows[y][x] = !rows[y][x];
+    let cleans_up = reflections(&transpose(&rows)) == [down];
+    rows[y][x] = !rows[y][x];
+    (reflects_once && cleans_up).then_some(rows)
+}
+
+/// `size` patterns of 5 to 17 tiles a side, each with a line of reflection and a smudge.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let patterns: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let rows = loop {
+                if let Some(rows) = pattern(&mut rng) {
+                    break rows;
+                }
+            };
+            let rows: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect()
+                })
+                .collect();
+            rows.join("\n")
+        })
+        .collect();
+    patterns.join("\n\n")
+}
@@ -1,17 +1,14 @@
+use aoc_common::{input, Answer, AocError, Solution};
+use aoc_grid::Grid;
+
 #[derive(Debug, PartialEq, Eq)]
-enum Mirror {
+pub enum Mirror {
     Vertical(usize),
     Horizontal(usize),
 }
 
-fn rotate_matrix(input: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    (0..input[0].len())
-        .map(|i| input.iter().map(|row| row[i]).collect())
-        .collect()
-}
-
-//fn find_split(input: Vec<Vec<char>>) -> Option<usize> {
-fn find_split(input: &[Vec<char>]) -> Option<usize> {
+fn find_split(grid: &Grid<char>) -> Option<usize> {
+    let input: Vec<&[char]> = grid.rows().collect();
     for i in 0..input.len() - 1 {
         if input[i] == input[i + 1] {
             let mut match_found = true;
@@ -48,29 +45,59 @@ fn find_split(input: &[Vec<char>]) -> Option<usize> {
     None
 }
 
-fn find_mirror(input: Vec<Vec<char>>) -> Mirror {
+fn find_mirror(input: Grid<char>) -> Option<Mirror> {
     if let Some(i) = find_split(&input) {
-        return Mirror::Horizontal(i);
+        return Some(Mirror::Horizontal(i));
     }
-    let rotated = rotate_matrix(input);
-    if let Some(i) = find_split(&rotated) {
-        return Mirror::Vertical(i);
-    }
-    panic!("No mirror found");
+    let rotated = input.transpose();
+    find_split(&rotated).map(Mirror::Vertical)
 }
 
-pub fn process(input: &str) -> String {
-    let mut mirrors: Vec<Mirror> = vec![];
-    let chunks: Vec<&str> = input.split("\n\n").collect();
-    for chunk in chunks {
-        let map: Vec<Vec<char>> = chunk.lines().map(|line| line.chars().collect()).collect();
-        mirrors.push(find_mirror(map));
-    }
+fn parse_pattern(input: &str, chunk: &str) -> Result<Grid<char>, AocError> {
+    Grid::parse(chunk, |c| (c == '.' || c == '#').then_some(c)).map_err(|e| e.within(input, chunk))
+}
+
+/// Finds each pattern's mirror line, since a pattern without one is malformed.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Mirror>, AocError> {
+    input::blocks(input)
+        .into_iter()
+        .map(|chunk| {
+            let first_line = chunk.lines().next().unwrap_or(chunk);
+            find_mirror(parse_pattern(input, chunk)?)
+                .ok_or_else(|| AocError::at(input, first_line, "no mirror line"))
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(mirrors: Vec<Mirror>) -> Answer {
     let score = mirrors.iter().fold(0, |acc, m| match m {
         Mirror::Vertical(value) => acc + value,
         Mirror::Horizontal(value) => acc + (value * 100),
     });
-    score.to_string()
+    score.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 13;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<Mirror>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -78,7 +105,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "#.##..##.
 ..#.##.#.
 ##......#
@@ -94,7 +121,25 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        assert_eq!("405", process(input))
+        assert_eq!("405", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_windows_file() -> Result<(), AocError> {
+        let mirrors = parse("#.\r\n#.\r\n\r\n##\r\n..\r\n")?;
+        assert_eq!(mirrors, vec![Mirror::Horizontal(1), Mirror::Vertical(1)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_mirror() {
+        let error = process("##\n##\n\n#.\n.#\n##").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(
+            error.to_string(),
+            "day 13: line 4, column 1: no mirror line at `#.`"
+        );
     }
 
     #[test]
@@ -106,7 +151,7 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let map = Grid::parse(input, Some).unwrap();
         assert_eq!(Some(4), find_split(&map));
     }
 
@@ -119,24 +164,15 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.";
-        let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        assert_eq!(Mirror::Vertical(5), find_mirror(map));
+        let map = Grid::parse(input, Some).unwrap();
+        assert_eq!(Some(Mirror::Vertical(5)), find_mirror(map));
     }
 
     #[test]
     fn test_rotate_1() {
-        let map_vec = vec![vec!['1', '2', '2', '2', '3'], vec!['1', '2', '4', '4', '4']];
-        let rotated = rotate_matrix(map_vec);
-        assert_eq!(
-            vec![
-                vec!['1', '1'],
-                vec!['2', '2'],
-                vec!['2', '4'],
-                vec!['2', '4'],
-                vec!['3', '4']
-            ],
-            rotated
-        );
+        let map = Grid::parse("12223\n12444", Some).unwrap();
+        let rotated = map.transpose();
+        assert_eq!("11\n22\n24\n24\n34", rotated.to_string());
     }
 
     #[test]
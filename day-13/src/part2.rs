@@ -1,16 +1,14 @@
+use aoc_common::{input, Answer, AocError, Solution};
+use aoc_grid::Grid;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Mirror {
+pub enum Mirror {
     Vertical(usize),
     Horizontal(usize),
 }
 
-fn rotate_matrix(input: &[Vec<char>]) -> Vec<Vec<char>> {
-    (0..input[0].len())
-        .map(|i| input.iter().map(|row| row[i]).collect())
-        .collect()
-}
-
-fn find_splits(input: &[Vec<char>]) -> Vec<usize> {
+fn find_splits(grid: &Grid<char>) -> Vec<usize> {
+    let input: Vec<&[char]> = grid.rows().collect();
     let mut splits: Vec<usize> = vec![];
     for i in 0..input.len() - 1 {
         if input[i] == input[i + 1] {
@@ -48,14 +46,14 @@ fn find_splits(input: &[Vec<char>]) -> Vec<usize> {
     splits
 }
 
-fn find_mirrors(input: &[Vec<char>]) -> Vec<Mirror> {
+fn find_mirrors(input: &Grid<char>) -> Vec<Mirror> {
     let mut mirrors: Vec<Mirror> = vec![];
 
     find_splits(input)
         .iter()
         .for_each(|val| mirrors.push(Mirror::Horizontal(*val)));
 
-    let rotated = rotate_matrix(input);
+    let rotated = input.transpose();
     find_splits(&rotated)
         .iter()
         .for_each(|val| mirrors.push(Mirror::Vertical(*val)));
@@ -63,49 +61,93 @@ fn find_mirrors(input: &[Vec<char>]) -> Vec<Mirror> {
     mirrors
 }
 
-fn find_smudge_mirror(mut input: Vec<Vec<char>>) -> Mirror {
-    let og_mirrors = find_mirrors(&input);
-    if og_mirrors.len() > 1 {
-        panic!("More than one mirror");
-    }
-    let og_mirror = og_mirrors.first().unwrap();
+/// Why a pattern has no answer.
+#[derive(Debug, PartialEq, Eq)]
+enum NoSmudge {
+    NoMirror,
+    SeveralMirrors,
+    /// No single flipped tile gives a new mirror line.
+    Unchanged,
+}
 
-    for i in 0..input.len() {
-        for j in 0..input[i].len() {
-            let og_val = input[i][j];
+fn find_smudge_mirror(mut input: Grid<char>) -> Result<Mirror, NoSmudge> {
+    let og_mirror = match find_mirrors(&input)[..] {
+        [] => return Err(NoSmudge::NoMirror),
+        [mirror] => mirror,
+        _ => return Err(NoSmudge::SeveralMirrors),
+    };
 
-            input[i][j] = match og_val {
-                '.' => '#',
-                '#' => '.',
-                _ => panic!("invalid input"),
-            };
+    for i in 0..input.height() {
+        for j in 0..input.width() {
+            let og_val = input[(j, i)];
+            input[(j, i)] = if og_val == '.' { '#' } else { '.' };
 
             let splits = find_mirrors(&input);
 
             for mirror in &splits {
-                if mirror != og_mirror {
-                    return *mirror;
+                if *mirror != og_mirror {
+                    return Ok(*mirror);
                 }
             }
-            input[i][j] = og_val;
+            input[(j, i)] = og_val;
         }
     }
 
-    panic!("Could not find split");
+    Err(NoSmudge::Unchanged)
 }
 
-pub fn process(input: &str) -> String {
-    let mut mirrors: Vec<Mirror> = vec![];
-    let chunks: Vec<&str> = input.split("\n\n").collect();
-    for chunk in chunks {
-        let map: Vec<Vec<char>> = chunk.lines().map(|line| line.chars().collect()).collect();
-        mirrors.push(find_smudge_mirror(map));
-    }
+fn parse_pattern(input: &str, chunk: &str) -> Result<Grid<char>, AocError> {
+    Grid::parse(chunk, |c| (c == '.' || c == '#').then_some(c)).map_err(|e| e.within(input, chunk))
+}
+
+/// Finds each pattern's mirror line once its smudge is cleaned, since a pattern without one is
+/// malformed.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Mirror>, AocError> {
+    input::blocks(input)
+        .into_iter()
+        .map(|chunk| {
+            let first_line = chunk.lines().next().unwrap_or(chunk);
+            find_smudge_mirror(parse_pattern(input, chunk)?).map_err(|e| {
+                let message = match e {
+                    NoSmudge::NoMirror => "no mirror line",
+                    NoSmudge::SeveralMirrors => "more than one mirror line",
+                    NoSmudge::Unchanged => "no smudge moves the mirror line",
+                };
+                AocError::at(input, first_line, message)
+            })
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(mirrors: Vec<Mirror>) -> Answer {
     let score = mirrors.iter().fold(0, |acc, m| match m {
         Mirror::Vertical(value) => acc + value,
         Mirror::Horizontal(value) => acc + (value * 100),
     });
-    score.to_string()
+    score.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 13;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<Mirror>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -113,7 +155,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "#.##..##.
 ..#.##.#.
 ##......#
@@ -129,7 +171,8 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        assert_eq!("400", process(input))
+        assert_eq!("400", process(input)?);
+        Ok(())
     }
 
     #[test]
@@ -141,8 +184,8 @@ mod tests {
 ..#.##.#.
 ..##..##.
 #.#.##.#.";
-        let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        assert_eq!(Mirror::Horizontal(3), find_smudge_mirror(map));
+        let map = Grid::parse(input, Some).unwrap();
+        assert_eq!(Mirror::Horizontal(3), find_smudge_mirror(map).unwrap());
     }
 
     #[test]
@@ -154,7 +197,19 @@ mod tests {
 #####.##.
 ..##..###
 #....#..#";
-        let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        assert_eq!(Mirror::Horizontal(1), find_smudge_mirror(map));
+        let map = Grid::parse(input, Some).unwrap();
+        assert_eq!(Mirror::Horizontal(1), find_smudge_mirror(map).unwrap());
+    }
+
+    #[test]
+    fn test_no_smudge() {
+        let error = process("#.#\n##.").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 13: line 1, column 1: no mirror line at `#.#`"
+        );
+
+        let map = Grid::parse("##\n##", Some).unwrap();
+        assert_eq!(find_smudge_mirror(map), Err(NoSmudge::SeveralMirrors));
     }
 }
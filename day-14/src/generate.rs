@@ -0,0 +1,25 @@
+//! Made-up reflector dishes, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "grid side";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[25, 50, 100, 200];
+
+/// A `size` by `size` dish, about a fifth round rocks and a sixth cube rocks.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let rows: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| match rng.below(30) {
+                    0..=5 => 'O',
+                    6..=10 => '#',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
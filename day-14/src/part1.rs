@@ -1,42 +1,43 @@
+use aoc_common::{Answer, AocError, Solution};
+use aoc_grid::Grid;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Thing {
+pub enum Thing {
     Sphere,
     Cube,
     Empty,
 }
 
 impl Thing {
-    fn from_char(c: char) -> Thing {
+    fn from_char(c: char) -> Option<Thing> {
         match c {
-            'O' => Thing::Sphere,
-            '#' => Thing::Cube,
-            '.' => Thing::Empty,
-            _ => panic!("invalid char: {}", c),
+            'O' => Some(Thing::Sphere),
+            '#' => Some(Thing::Cube),
+            '.' => Some(Thing::Empty),
+            _ => None,
         }
     }
 }
 
-fn parse_platform(input: &str) -> Vec<Vec<Thing>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(Thing::from_char).collect())
-        .collect()
+#[tracing::instrument(skip_all)]
+fn parse_platform(input: &str) -> Result<Grid<Thing>, AocError> {
+    Grid::parse(input, Thing::from_char)
 }
 
-fn tilt_platform(mut platform: Vec<Vec<Thing>>) -> Vec<Vec<Thing>> {
-    for i in 1..platform.len() {
-        for j in 0..platform[i].len() {
-            if platform[i][j] == Thing::Sphere {
+fn tilt_platform(mut platform: Grid<Thing>) -> Grid<Thing> {
+    for i in 1..platform.height() {
+        for j in 0..platform.width() {
+            if platform[(j, i)] == Thing::Sphere {
                 let mut movable = true;
                 let mut i_index = i;
                 while movable {
                     if i_index == 0 {
                         movable = false;
-                    } else if platform[i_index][j] == Thing::Sphere
-                        && platform[i_index - 1][j] == Thing::Empty
+                    } else if platform[(j, i_index)] == Thing::Sphere
+                        && platform[(j, i_index - 1)] == Thing::Empty
                     {
-                        platform[i_index - 1][j] = Thing::Sphere;
-                        platform[i_index][j] = Thing::Empty;
+                        platform[(j, i_index - 1)] = Thing::Sphere;
+                        platform[(j, i_index)] = Thing::Empty;
                         i_index -= 1;
                     } else {
                         movable = false;
@@ -48,11 +49,11 @@ fn tilt_platform(mut platform: Vec<Vec<Thing>>) -> Vec<Vec<Thing>> {
     platform
 }
 
-fn calculate_load(platform: &[Vec<Thing>]) -> usize {
-    let total_rows = platform.len();
+fn calculate_load(platform: &Grid<Thing>) -> usize {
+    let total_rows = platform.height();
 
     platform
-        .iter()
+        .rows()
         .enumerate()
         .map(|(i, row)| {
             row.iter().filter(|&thing| *thing == Thing::Sphere).count() * (total_rows - i)
@@ -60,10 +61,31 @@ fn calculate_load(platform: &[Vec<Thing>]) -> usize {
         .sum()
 }
 
-pub fn process(input: &str) -> String {
-    let platform = parse_platform(input);
+#[tracing::instrument(skip_all)]
+pub fn solve(platform: Grid<Thing>) -> Answer {
     let load = calculate_load(&tilt_platform(platform));
-    load.to_string()
+    load.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 14;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Grid<Thing>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse_platform(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -71,7 +93,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "O....#....
 O.OO#....#
 .....##...
@@ -82,25 +104,44 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!("136", process(input))
+        assert_eq!("136", process(input)?);
+        Ok(())
     }
 
     #[test]
-    fn test_parse_platform() {
+    fn test_parse_platform() -> Result<(), AocError> {
         let input = "O..#
 .O.#";
-        let platform = parse_platform(input);
+        let platform = parse_platform(input)?;
         assert_eq!(
             platform,
-            vec![
-                vec![Thing::Sphere, Thing::Empty, Thing::Empty, Thing::Cube],
-                vec![Thing::Empty, Thing::Sphere, Thing::Empty, Thing::Cube]
-            ]
-        )
+            Grid::from_vec(
+                4,
+                vec![
+                    Thing::Sphere,
+                    Thing::Empty,
+                    Thing::Empty,
+                    Thing::Cube,
+                    Thing::Empty,
+                    Thing::Sphere,
+                    Thing::Empty,
+                    Thing::Cube
+                ]
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_platform_bad_tile() {
+        let input = "O..#\n.X.#";
+        let error = parse_platform(input).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.token, "X");
     }
 
     #[test]
-    fn test_tilt_platform() {
+    fn test_tilt_platform() -> Result<(), AocError> {
         let input = ".
 O
 .
@@ -110,13 +151,14 @@ O
 #
 .
 O";
-        let platform = parse_platform(input);
+        let platform = parse_platform(input)?;
         let tilt = tilt_platform(platform);
         println!("{:?}", tilt);
-        assert_eq!(Thing::Sphere, tilt[0][0]);
-        assert_eq!(Thing::Sphere, tilt[1][0]);
-        assert_eq!(Thing::Sphere, tilt[2][0]);
-        assert_eq!(Thing::Empty, tilt[3][0]);
-        assert_eq!(Thing::Empty, tilt[4][0]);
+        assert_eq!(Thing::Sphere, tilt[(0, 0)]);
+        assert_eq!(Thing::Sphere, tilt[(0, 1)]);
+        assert_eq!(Thing::Sphere, tilt[(0, 2)]);
+        assert_eq!(Thing::Empty, tilt[(0, 3)]);
+        assert_eq!(Thing::Empty, tilt[(0, 4)]);
+        Ok(())
     }
 }
@@ -1,53 +1,45 @@
-use std::collections::{HashMap, VecDeque};
+use aoc_common::{cycle, Answer, AocError, Solution};
+use aoc_geometry::Direction;
+use aoc_grid::Grid;
+use std::fmt;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Thing {
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Thing {
     Sphere,
     Cube,
     Empty,
 }
 
 impl Thing {
-    fn from_char(c: char) -> Thing {
+    fn from_char(c: char) -> Option<Thing> {
         match c {
-            'O' => Thing::Sphere,
-            '#' => Thing::Cube,
-            '.' => Thing::Empty,
-            _ => panic!("invalid char: {}", c),
+            'O' => Some(Thing::Sphere),
+            '#' => Some(Thing::Cube),
+            '.' => Some(Thing::Empty),
+            _ => None,
         }
     }
 }
 
-fn parse_platform(input: &str) -> Vec<Vec<Thing>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(Thing::from_char).collect())
-        .collect()
+impl fmt::Display for Thing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Thing::Sphere => 'O',
+            Thing::Cube => '#',
+            Thing::Empty => '.',
+        };
+        write!(f, "{}", symbol)
+    }
 }
 
-fn rotate_platform(platform: &mut Vec<Vec<Thing>>, clockwise: bool) {
-    let cols = platform[0].len();
-
-    let mut rotated: Vec<Vec<_>> = if clockwise {
-        (0..cols)
-            .map(|i| platform.iter().map(|row| row[i]).collect())
-            .collect()
-    } else {
-        (0..cols)
-            .rev()
-            .map(|i| platform.iter().map(|row| row[i]).collect())
-            .collect()
-    };
-
-    if !clockwise {
-        rotated.reverse();
-    }
-    *platform = rotated;
+#[tracing::instrument(skip_all)]
+fn parse_platform(input: &str) -> Result<Grid<Thing>, AocError> {
+    Grid::parse(input, Thing::from_char)
 }
 
-fn tilt_platform_horizontal(platform: &mut [Vec<Thing>], left: bool) {
-    let row_len = platform[0].len();
-    for row in platform.iter_mut() {
+fn tilt_platform_horizontal(platform: &mut Grid<Thing>, left: bool) {
+    let row_len = platform.width();
+    for row in platform.rows_mut() {
         let mut new_row = Vec::with_capacity(row_len);
         let chunks: Vec<Vec<Thing>> = row
             .split(|&t| t == Thing::Cube)
@@ -71,43 +63,48 @@ fn tilt_platform_horizontal(platform: &mut [Vec<Thing>], left: bool) {
                 new_row.push(Thing::Cube);
             }
         }
-        *row = new_row;
+        row.copy_from_slice(&new_row);
     }
 }
 
-fn tilt_platform_vertical(platform: &mut Vec<Vec<Thing>>, up: bool) {
-    rotate_platform(platform, true);
+fn tilt_platform_vertical(platform: &mut Grid<Thing>, up: bool) {
+    *platform = platform.transpose();
     if up {
         tilt_platform_horizontal(platform, true);
     } else {
         tilt_platform_horizontal(platform, false);
     }
-    rotate_platform(platform, false);
+    *platform = platform.transpose();
 }
 
-/// tilt platform in any direction
-/// direction: 0: North, 1: West, 2: South, 3: East
-fn tilt_platform_generic(platform: &mut Vec<Vec<Thing>>, direction: usize) {
+/// A spin cycle tilts north, then west, then south, then east.
+const CYCLE: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
+
+fn tilt_platform(platform: &mut Grid<Thing>, direction: Direction) {
     match direction {
-        0 => tilt_platform_vertical(platform, true),
-        1 => tilt_platform_horizontal(platform, true),
-        2 => tilt_platform_vertical(platform, false),
-        3 => tilt_platform_horizontal(platform, false),
-        _ => panic!("uh oh what direciton is this?"),
+        Direction::Up => tilt_platform_vertical(platform, true),
+        Direction::Left => tilt_platform_horizontal(platform, true),
+        Direction::Down => tilt_platform_vertical(platform, false),
+        Direction::Right => tilt_platform_horizontal(platform, false),
     }
 }
 
-fn run_cycle(platform: &mut Vec<Vec<Thing>>) {
-    for i in 0..4 {
-        tilt_platform_generic(platform, i);
+fn run_cycle(platform: &mut Grid<Thing>) {
+    for direction in CYCLE {
+        tilt_platform(platform, direction);
     }
 }
 
-fn calculate_load(platform: &[Vec<Thing>]) -> usize {
-    let total_rows = platform.len();
+fn calculate_load(platform: &Grid<Thing>) -> usize {
+    let total_rows = platform.height();
 
     platform
-        .iter()
+        .rows()
         .enumerate()
         .map(|(i, row)| {
             row.iter().filter(|&thing| *thing == Thing::Sphere).count() * (total_rows - i)
@@ -115,54 +112,35 @@ fn calculate_load(platform: &[Vec<Thing>]) -> usize {
         .sum()
 }
 
-pub fn process(input: &str) -> String {
-    let endpoint = 1000000000;
-    let mut platform = parse_platform(input);
+#[tracing::instrument(skip_all)]
+pub fn solve(platform: Grid<Thing>) -> Answer {
+    let spin = |platform: &Grid<Thing>| {
+        let mut next = platform.clone();
+        run_cycle(&mut next);
+        next
+    };
+    let platform = cycle::nth_hashed(platform, spin, 1_000_000_000);
+    calculate_load(&platform).into()
+}
 
-    // From scientifically looking at the data, we observe a cycle appears that is len 7
-    let cycle_length = 7;
-    let mut seen_sequences = HashMap::new();
-    let mut buffer = VecDeque::with_capacity(cycle_length);
-    let mut cycle_start = 0;
-    let mut final_sequence = Vec::new();
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
 
-    for iteration in 0..endpoint {
-        run_cycle(&mut platform);
-        let load = calculate_load(&platform);
+pub struct Part2;
 
-        buffer.push_back(load);
+impl Solution for Part2 {
+    const DAY: u8 = 14;
+    const PART: u8 = 2;
 
-        if buffer.len() == cycle_length {
-            let sequence = buffer.iter().copied().collect::<Vec<_>>();
+    type Parsed<'a> = Grid<Thing>;
 
-            if let Some(&start) = seen_sequences.get(&sequence) {
-                cycle_start = start;
-                final_sequence = sequence;
-                break;
-            } else {
-                seen_sequences.insert(sequence, iteration);
-                buffer.pop_front();
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse_platform(input)
     }
 
-    let sequence_index = (endpoint - cycle_start - 2) % final_sequence.len();
-    let load = final_sequence[sequence_index];
-    load.to_string()
-}
-
-#[allow(dead_code)]
-fn print_platform(platform: &Vec<Vec<Thing>>) {
-    for row in platform {
-        for &thing in row {
-            let symbol = match thing {
-                Thing::Empty => ".",
-                Thing::Cube => "#",
-                Thing::Sphere => "o",
-            };
-            print!("{}", symbol);
-        }
-        println!(); // Move to the next line after each row
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
     }
 }
 
@@ -171,7 +149,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "O....#....
 O.OO#....#
 .....##...
@@ -182,42 +160,43 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        assert_eq!("64", process(input))
+        assert_eq!("64", process(input)?);
+        Ok(())
     }
 
     #[test]
-    fn test_tilt_platform_left() {
+    fn test_tilt_platform_left() -> Result<(), AocError> {
         let input = "..OO#.O.O
 ..###.O.O";
-        let mut platform = parse_platform(input);
+        let mut platform = parse_platform(input)?;
         tilt_platform_horizontal(&mut platform, true);
         let output = "OO..#OO..
 ..###OO..";
-        let expected = parse_platform(output);
-        assert_eq!(platform.len(), 2);
-        assert_eq!(platform[0].len(), 9);
-        assert_eq!(platform[1].len(), 9);
+        let expected = parse_platform(output)?;
+        assert_eq!(platform.height(), 2);
+        assert_eq!(platform.width(), 9);
         assert_eq!(platform, expected);
+        Ok(())
     }
 
     #[test]
-    fn test_tilt_platform_right() {
+    fn test_tilt_platform_right() -> Result<(), AocError> {
         let input = "..OO#.O.O
 ..###.O.O";
-        let mut platform = parse_platform(input);
+        let mut platform = parse_platform(input)?;
         tilt_platform_horizontal(&mut platform, false);
-        assert_eq!(platform.len(), 2);
-        assert_eq!(platform[0].len(), 9);
-        assert_eq!(platform[1].len(), 9);
+        assert_eq!(platform.height(), 2);
+        assert_eq!(platform.width(), 9);
         let output = "..OO#..OO
 ..###..OO";
 
-        let expected = parse_platform(output);
+        let expected = parse_platform(output)?;
         assert_eq!(platform, expected);
+        Ok(())
     }
 
     #[test]
-    fn test_tilt_platform_up() {
+    fn test_tilt_platform_up() -> Result<(), AocError> {
         let input = "..
 OO
 #.
@@ -225,7 +204,7 @@ OO
 O#
 ..
 ";
-        let mut platform = parse_platform(input);
+        let mut platform = parse_platform(input)?;
         tilt_platform_vertical(&mut platform, true);
         let output = "OO
 .O
@@ -234,12 +213,13 @@ O.
 .#
 ..
 ";
-        let expected = parse_platform(output);
+        let expected = parse_platform(output)?;
         assert_eq!(platform, expected);
+        Ok(())
     }
 
     #[test]
-    fn test_cycle_1() {
+    fn test_cycle_1() -> Result<(), AocError> {
         let input = "O....#....
 O.OO#....#
 .....##...
@@ -250,17 +230,18 @@ O.#..O.#.#
 .......O..
 #....###..
 #OO..#....";
-        let mut platform = parse_platform(input);
+        let mut platform = parse_platform(input)?;
         run_cycle(&mut platform);
         println!("1 cycle");
-        print_platform(&platform);
+        println!("{}", platform);
 
         run_cycle(&mut platform);
         println!("2 cycle");
-        print_platform(&platform);
+        println!("{}", platform);
 
         run_cycle(&mut platform);
         println!("3 cycle");
-        print_platform(&platform);
+        println!("{}", platform);
+        Ok(())
     }
 }
@@ -0,0 +1,31 @@
+//! Made-up initialization sequences, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "steps";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[4_000, 16_000, 64_000, 256_000];
+
+/// `size` steps over a pool of about `size / 4` labels, so lenses get replaced and removed.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let labels: Vec<String> = (0..size / 4 + 1)
+        .map(|_| {
+            (0..rng.range(2..7))
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let label = rng.pick(&labels);
+            if rng.chance(0.6) {
+                format!("{}={}", label, rng.range(1..10))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .collect();
+    steps.join(",")
+}
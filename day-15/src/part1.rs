@@ -1,12 +1,42 @@
+use aoc_common::{Answer, AocError, Solution};
+
 fn hash_string(input: &str) -> u32 {
     input
         .chars()
         .fold(0, |acc, c| ((acc + (c as u32)) * 17) % 256)
 }
 
-pub fn process(input: &str) -> String {
-    let sum: u32 = input.split(',').map(hash_string).sum();
-    sum.to_string()
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<&str>, AocError> {
+    // One long line, though editors may leave a newline at the end of it
+    Ok(input.split(',').map(str::trim).collect())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(steps: Vec<&str>) -> Answer {
+    let sum: u32 = steps.into_iter().map(hash_string).sum();
+    sum.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part1::process(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 15;
+    const PART: u8 = 1;
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -14,9 +44,16 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!("1320", process(input))
+        assert_eq!("1320", process(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_trailing_newline() -> Result<(), AocError> {
+        assert_eq!(parse("rn=1,cm-\n")?, vec!["rn=1", "cm-"]);
+        Ok(())
     }
 
     #[test]
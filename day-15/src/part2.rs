@@ -1,5 +1,8 @@
+use aoc_common::error::number;
+use aoc_common::{Answer, AocError, Solution};
+
 #[derive(Debug, Clone, PartialEq)]
-struct Lens {
+pub struct Lens {
     label: String,
     focal: u8,
 }
@@ -26,29 +29,49 @@ fn box_focus_power(box_id: usize, lens_box: &[Lens]) -> u64 {
     })
 }
 
-pub fn process(input: &str) -> String {
+pub enum Step<'a> {
+    Remove(&'a str),
+    Insert(Lens),
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> Result<Vec<Step<'_>>, AocError> {
+    input
+        .split(',')
+        .map(str::trim)
+        .map(|step| {
+            if let Some((label, _)) = step.split_once('-') {
+                Ok(Step::Remove(label))
+            } else if let Some((_, focal)) = step.split_once('=') {
+                number::<u8>(input, focal)?;
+                Ok(Step::Insert(Lens::from_str(step)))
+            } else {
+                Err(AocError::at(input, step, "unknown action"))
+            }
+        })
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(steps: Vec<Step>) -> Answer {
     let mut boxes: Vec<Vec<Lens>> = vec![vec![]; 256];
-    let steps: Vec<_> = input.split(',').collect();
 
     for step in steps {
-        let end_index = step.find('-').or(step.find('=')).unwrap();
-        let label = &step[..end_index];
-        let box_id = hash_string(label) as usize;
-        let lens_box = boxes.get_mut(box_id).unwrap();
-
-        if step.contains('-') {
-            lens_box.retain(|lens| lens.label != label);
-        } else if step.contains('=') {
-            let lens = Lens::from_str(step);
-            let lens_index = lens_box.iter().position(|l| l.label == lens.label);
-
-            if let Some(index) = lens_index {
-                lens_box[index] = lens;
-            } else {
-                lens_box.push(lens);
+        match step {
+            Step::Remove(label) => {
+                let lens_box = boxes.get_mut(hash_string(label) as usize).unwrap();
+                lens_box.retain(|lens| lens.label != label);
+            }
+            Step::Insert(lens) => {
+                let lens_box = boxes.get_mut(hash_string(&lens.label) as usize).unwrap();
+                let lens_index = lens_box.iter().position(|l| l.label == lens.label);
+
+                if let Some(index) = lens_index {
+                    lens_box[index] = lens;
+                } else {
+                    lens_box.push(lens);
+                }
             }
-        } else {
-            panic!("unknown action: {}", step);
         }
     }
 
@@ -56,7 +79,28 @@ pub fn process(input: &str) -> String {
         .iter()
         .enumerate()
         .fold(0, |acc, (e, lens_box)| acc + box_focus_power(e, lens_box));
-    sum.to_string()
+    sum.into()
+}
+
+pub fn process(input: &str) -> Result<Answer, AocError> {
+    Part2::process(input)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 15;
+    const PART: u8 = 2;
+
+    type Parsed<'a> = Vec<Step<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, AocError> {
+        parse(input)
+    }
+
+    fn solve(parsed: Self::Parsed<'_>) -> Answer {
+        solve(parsed)
+    }
 }
 
 #[cfg(test)]
@@ -64,9 +108,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
+    fn test_process() -> Result<(), AocError> {
         let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!("145", process(input))
+        assert_eq!("145", process(input)?);
+        Ok(())
     }
 
     #[test]
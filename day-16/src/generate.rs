@@ -0,0 +1,27 @@
+//! Made-up mirror contraptions, for timing how the solutions scale.
+
+use aoc_common::rng::Rng;
+
+/// What `size` counts.
+pub const SIZE_UNIT: &str = "grid side";
+/// Sizes `runner scale` tries when none are given.
+pub const SIZES: &[usize] = &[14, 28, 55, 110];
+
+/// A `size` by `size` contraption with a mirror or splitter in about one tile in eight.
+pub fn generate(size: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+    let rows: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| match rng.below(32) {
+                    0 => '/',
+                    1 => '\\',
+                    2 => '|',
+                    3 => '-',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
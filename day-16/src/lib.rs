@@ -1,2 +1,6 @@
+/// The puzzle's title on adventofcode.com.
+pub const TITLE: &str = "The Floor Will Be Lava";
+
+pub mod generate;
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,166 @@
+//! Allocation counting for the runner.
+//!
+//! With the `alloc-stats` feature the runner installs [`CountingAllocator`] as the global
+//! allocator, and every timed run also reports how much it allocated. The counters are shared by
+//! the whole process, so the numbers only mean something while one job runs at a time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+use serde::{Deserialize, Serialize};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// The system allocator, counting every allocation on the way through.
+pub struct CountingAllocator;
+
+fn allocated(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size, Relaxed);
+    let live = LIVE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            // A growing Vec reallocates, so count it like a fresh allocation of the new size
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// What one run allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: usize,
+    /// Total bytes handed out, counting memory that was later freed.
+    pub bytes: usize,
+    /// The most memory the run held at once, on top of what was live when it started.
+    pub peak: usize,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        )
+    }
+}
+
+/// A byte count in the largest binary unit that keeps it above 1.
+pub struct Bytes(pub usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Counts allocations from when it's started until [`Counter::finish`].
+pub struct Counter {
+    allocations: usize,
+    bytes: usize,
+    live: usize,
+}
+
+impl Counter {
+    pub fn start() -> Self {
+        let live = LIVE.load(Relaxed);
+        PEAK.store(live, Relaxed);
+        Counter {
+            allocations: ALLOCATIONS.load(Relaxed),
+            bytes: BYTES.load(Relaxed),
+            live,
+        }
+    }
+
+    /// The counts since [`Counter::start`], or `None` if the counting allocator isn't installed.
+    pub fn finish(self) -> Option<AllocStats> {
+        cfg!(feature = "alloc-stats").then(|| AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed) - self.allocations,
+            bytes: BYTES.load(Relaxed) - self.bytes,
+            peak: PEAK.load(Relaxed).saturating_sub(self.live),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 3 * 1024 * 1024 + 512 * 1024,
+            peak: 900,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "12 allocs, 3.5 MiB allocated, 900 B peak"
+        );
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_counter() {
+        // Other tests allocate on their own threads, so only check lower bounds on the totals
+        let counter = Counter::start();
+        let mut rows: Vec<Vec<u8>> = Vec::new();
+        for _ in 0..10 {
+            rows.push(vec![0; 1000]);
+        }
+        drop(rows);
+        let stats = counter.finish().unwrap();
+        assert!(stats.allocations >= 10, "{:?}", stats);
+        assert!(stats.bytes >= 10_000, "{:?}", stats);
+    }
+}
@@ -0,0 +1,438 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use aoc_common::AocError;
+use serde::{Deserialize, Serialize};
+
+use crate::isolate::{self, Outcome};
+use crate::{Job, Phases};
+
+/// How long to warm up and measure each job for.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub warmup: Duration,
+    pub target: Duration,
+    pub min_iterations: usize,
+    pub max_iterations: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            warmup: Duration::from_millis(100),
+            target: Duration::from_secs(1),
+            min_iterations: 5,
+            max_iterations: 10_000,
+        }
+    }
+}
+
+/// Summary statistics over a set of samples, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "need at least one sample");
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(f64::total_cmp);
+
+        let n = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let median = if n.is_multiple_of(2) {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2.0
+        } else {
+            nanos[n / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = nanos[((n as f64 * 0.95).ceil() as usize).max(1) - 1];
+
+        Stats {
+            iterations: n,
+            min_ns: nanos[0] as u64,
+            median_ns: median as u64,
+            mean_ns: mean as u64,
+            stddev_ns: variance.sqrt() as u64,
+            p95_ns: p95 as u64,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        Duration::from_nanos(self.min_ns)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.mean_ns)
+    }
+
+    pub fn stddev(&self) -> Duration {
+        Duration::from_nanos(self.stddev_ns)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_nanos(self.p95_ns)
+    }
+}
+
+/// One line of a results file. `stats` covers a whole run; `parse` and `solve` break it down
+/// by phase, and are absent from results files written before phases were timed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    #[serde(flatten)]
+    pub stats: Stats,
+    #[serde(default)]
+    pub parse: Option<Stats>,
+    #[serde(default)]
+    pub solve: Option<Stats>,
+}
+
+/// Picks how many timed runs fit in `config.target`, given a per-run estimate.
+fn iterations_for(estimate: Duration, config: &Config) -> usize {
+    let fit = config.target.as_nanos() / estimate.as_nanos().max(1);
+    (fit as usize).clamp(config.min_iterations, config.max_iterations)
+}
+
+/// Warms `job` up, then times it enough times to fill the configured target duration.
+///
+/// A first run in isolation checks that `job` solves `input` within `timeout`. If it doesn't,
+/// that run's outcome comes back instead of a record.
+pub fn measure(
+    job: &'static Job,
+    input: &str,
+    config: &Config,
+    timeout: Option<Duration>,
+) -> Result<Record, Outcome> {
+    let probe = isolate::in_thread(job, input.to_string(), timeout).outcome;
+    if !probe.is_solved() {
+        return Err(probe);
+    }
+    let failed = |e: AocError| Outcome::Failed(e.to_string());
+
+    let warmup_start = Instant::now();
+    let mut warmup_runs = 0u32;
+    while warmup_runs == 0 || warmup_start.elapsed() < config.warmup {
+        job.run(input).map_err(failed)?;
+        warmup_runs += 1;
+    }
+    let estimate = warmup_start.elapsed() / warmup_runs;
+
+    let samples: Vec<Phases> = (0..iterations_for(estimate, config))
+        .map(|_| job.run_timed(input).map(|(_, phases)| phases))
+        .collect::<Result<_, _>>()
+        .map_err(failed)?;
+    let phase = |f: fn(&Phases) -> Duration| samples.iter().map(f).collect::<Vec<_>>();
+
+    Ok(Record {
+        day: job.day,
+        part: job.part,
+        stats: Stats::from_samples(&phase(Phases::total)),
+        parse: Some(Stats::from_samples(&phase(|p| p.parse))),
+        solve: Some(Stats::from_samples(&phase(|p| p.solve))),
+    })
+}
+
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let json = fs::read_to_string(path)?;
+    serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn save(path: &Path, records: &[Record]) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(records).expect("records serialize");
+    fs::write(path, json)
+}
+
+/// Whether a job got faster or slower than its baseline, beyond the noise threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Improved,
+    Unchanged,
+    Regressed,
+}
+
+/// A job's median time in a baseline run next to the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: u8,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Comparison {
+    /// Relative change of the median, in percent. Positive means slower.
+    pub fn delta_pct(&self) -> f64 {
+        (self.current_ns as f64 - self.baseline_ns as f64) / self.baseline_ns.max(1) as f64 * 100.0
+    }
+
+    /// Classifies the change, treating anything within `threshold_pct` as noise.
+    pub fn change(&self, threshold_pct: f64) -> Change {
+        let delta = self.delta_pct();
+        if delta > threshold_pct {
+            Change::Regressed
+        } else if delta < -threshold_pct {
+            Change::Improved
+        } else {
+            Change::Unchanged
+        }
+    }
+}
+
+/// Pairs up jobs present in both runs by day and part, comparing their medians.
+pub fn compare(baseline: &[Record], current: &[Record]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|record| {
+            baseline
+                .iter()
+                .find(|b| b.day == record.day && b.part == record.part)
+                .map(|b| Comparison {
+                    day: record.day,
+                    part: record.part,
+                    baseline_ns: b.stats.median_ns,
+                    current_ns: record.stats.median_ns,
+                })
+        })
+        .collect()
+}
+
+/// Absolute time limits per day, set with `--budget DAY=DURATION`.
+#[derive(Debug, Clone, Default)]
+pub struct Budgets {
+    pub per_day: Vec<(u8, Duration)>,
+}
+
+/// A day whose parts took longer, together, than its budget allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overrun {
+    pub day: u8,
+    pub took: Duration,
+    pub budget: Duration,
+}
+
+impl Budgets {
+    /// The budget for `day`; a later `--budget` for the same day wins.
+    pub fn for_day(&self, day: u8) -> Option<Duration> {
+        self.per_day
+            .iter()
+            .rev()
+            .find(|(d, _)| *d == day)
+            .map(|(_, budget)| *budget)
+    }
+
+    /// Days whose summed median over all measured parts exceeds their budget.
+    /// Days with no records in `records` are not checked.
+    pub fn over_budget(&self, records: &[Record]) -> Vec<Overrun> {
+        let mut days: Vec<u8> = self.per_day.iter().map(|(day, _)| *day).collect();
+        days.sort_unstable();
+        days.dedup();
+        days.into_iter()
+            .filter_map(|day| {
+                let budget = self.for_day(day)?;
+                let parts: Vec<&Record> = records.iter().filter(|r| r.day == day).collect();
+                if parts.is_empty() {
+                    return None;
+                }
+                let took = parts.iter().map(|r| r.stats.median()).sum();
+                (took > budget).then_some(Overrun { day, took, budget })
+            })
+            .collect()
+    }
+}
+
+/// Parses a duration such as `50ms`, `1.5s`, `200us` or `800ns`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| c.is_alphabetic())
+        .ok_or_else(|| format!("missing unit in `{}`, expected e.g. `50ms`", s))?;
+    let (value, unit) = s.split_at(split);
+    let nanos_per_unit = match unit {
+        "s" => 1e9,
+        "ms" => 1e6,
+        "us" | "µs" => 1e3,
+        "ns" => 1.0,
+        _ => return Err(format!("unknown unit `{}`, expected s, ms, us or ns", unit)),
+    };
+    let nanos = value
+        .trim()
+        .parse::<f64>()
+        .map(|v| v * nanos_per_unit)
+        .map_err(|_| format!("invalid duration `{}`", s))?;
+    if !(0.0..=u64::MAX as f64).contains(&nanos) {
+        return Err(format!("invalid duration `{}`", s));
+    }
+    Ok(Duration::from_nanos(nanos.round() as u64))
+}
+
+/// Parses a `DAY=DURATION` budget, e.g. `12=50ms`.
+pub fn parse_budget(s: &str) -> Result<(u8, Duration), String> {
+    let (day, budget) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected DAY=DURATION, got `{}`", s))?;
+    let day = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+    Ok((day, parse_duration(budget)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&ms(&[4, 2, 8, 6]));
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min(), Duration::from_millis(2));
+        assert_eq!(stats.median(), Duration::from_millis(5));
+        assert_eq!(stats.mean(), Duration::from_millis(5));
+        assert_eq!(stats.p95(), Duration::from_millis(8));
+        // sample stddev of 2, 4, 6, 8 is sqrt(20 / 3)
+        assert_eq!(stats.stddev_ns, 2_581_988);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&ms(&[3]));
+        assert_eq!(stats.min(), stats.p95());
+        assert_eq!(stats.median(), Duration::from_millis(3));
+        assert_eq!(stats.stddev_ns, 0);
+    }
+
+    #[test]
+    fn test_p95() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&ms(&samples));
+        assert_eq!(stats.p95(), Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_iterations_for() {
+        let config = Config::default();
+        assert_eq!(iterations_for(Duration::from_millis(10), &config), 100);
+        assert_eq!(iterations_for(Duration::from_secs(2), &config), 5);
+        assert_eq!(iterations_for(Duration::ZERO, &config), 10_000);
+    }
+
+    #[test]
+    fn test_record_json() {
+        let record = Record {
+            day: 7,
+            part: 2,
+            stats: Stats::from_samples(&ms(&[1])),
+            parse: None,
+            solve: None,
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.starts_with(r#"{"day":7,"part":2,"iterations":1,"min_ns":1000000"#));
+        assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+
+        // Results files from before phase timing have no parse/solve breakdown
+        let old = r#"{"day":7,"part":2,"iterations":1,"min_ns":1000000,"median_ns":1000000,
+            "mean_ns":1000000,"stddev_ns":0,"p95_ns":1000000}"#;
+        assert_eq!(serde_json::from_str::<Record>(old).unwrap(), record);
+    }
+
+    fn record(day: u8, part: u8, median_ms: u64) -> Record {
+        Record {
+            day,
+            part,
+            stats: Stats::from_samples(&ms(&[median_ms])),
+            parse: None,
+            solve: None,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = vec![record(1, 1, 100), record(1, 2, 100), record(2, 1, 10)];
+        let current = vec![record(1, 1, 50), record(1, 2, 103), record(3, 1, 10)];
+        let comparisons = compare(&baseline, &current);
+
+        assert_eq!(comparisons.len(), 2);
+        assert_eq!(comparisons[0].delta_pct(), -50.0);
+        assert_eq!(comparisons[0].change(5.0), Change::Improved);
+        assert_eq!(comparisons[1].change(5.0), Change::Unchanged);
+        assert_eq!(comparisons[1].change(2.0), Change::Regressed);
+    }
+
+    #[test]
+    fn test_budgets() {
+        let budgets = Budgets {
+            per_day: vec![
+                (12, Duration::from_millis(50)),
+                (16, Duration::from_millis(30)),
+            ],
+        };
+        let records = [
+            record(12, 1, 10),
+            record(12, 2, 30),
+            record(16, 1, 20),
+            record(16, 2, 20),
+        ];
+        assert_eq!(
+            budgets.over_budget(&records),
+            vec![Overrun {
+                day: 16,
+                took: Duration::from_millis(40),
+                budget: Duration::from_millis(30),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let budgets = Budgets {
+            per_day: vec![(12, Duration::from_millis(50))],
+        };
+        assert!(budgets.over_budget(&[record(12, 1, 50)]).is_empty());
+        let over = budgets.over_budget(&[record(12, 1, 51)]);
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].took, Duration::from_millis(51));
+
+        // Only the last budget for a day counts, and unmeasured days are skipped
+        let budgets = Budgets {
+            per_day: vec![
+                (12, Duration::from_millis(50)),
+                (12, Duration::from_millis(60)),
+            ],
+        };
+        assert!(budgets.over_budget(&[record(12, 1, 55)]).is_empty());
+        assert!(budgets.over_budget(&[record(11, 1, 500)]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_budget() {
+        assert_eq!(parse_budget("12=50ms"), Ok((12, Duration::from_millis(50))));
+        assert_eq!(parse_budget("5=1.5s"), Ok((5, Duration::from_millis(1500))));
+        assert_eq!(parse_budget("5=200us"), Ok((5, Duration::from_micros(200))));
+        assert_eq!(parse_budget("5=200µs"), Ok((5, Duration::from_micros(200))));
+        assert_eq!(parse_budget("5=800ns"), Ok((5, Duration::from_nanos(800))));
+        assert!(parse_budget("16").is_err());
+        assert!(parse_budget("x=1ms").is_err());
+        assert!(parse_budget("16=25").is_err());
+        assert!(parse_budget("16=25m").is_err());
+        assert!(parse_budget("16=-5ms").is_err());
+    }
+}
@@ -1,27 +1,165 @@
-use took::{Timer, Took};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
 
-const RUNS: usize = 100;
+use clap::Parser;
+use runner::bench::{self, Budgets, Change, Config, Record};
+use runner::isolate;
+use took::Took;
 
-fn main() {
-    println!("Benchmarking all days with {} runs...", RUNS);
+#[derive(Parser)]
+#[command(about = "Benchmark Advent of Code solutions")]
+struct Cli {
+    /// Only benchmark this day
+    #[arg(long)]
+    day: Option<u8>,
+    /// Time spent running each job before measuring, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    warmup_ms: u64,
+    /// Time to spend measuring each job, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    target_ms: u64,
+    #[arg(long, default_value_t = 5)]
+    min_iterations: usize,
+    #[arg(long, default_value_t = 10_000)]
+    max_iterations: usize,
+    /// Seconds a job may take to solve its input once before it's skipped
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// Where to write the JSON results
+    #[arg(long, default_value = "bench.json")]
+    output: PathBuf,
+    /// Also save the results as a baseline for later comparison
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+    /// Compare against a previously saved baseline
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Median changes smaller than this percentage are reported as noise
+    #[arg(long, default_value_t = 5.0)]
+    threshold: f64,
+    /// Fail if a day's parts take longer than this together, as DAY=DURATION
+    /// (e.g. `12=50ms`). May be repeated
+    #[arg(long = "budget", value_parser = bench::parse_budget)]
+    budgets: Vec<(u8, Duration)>,
+}
 
-    let times: Vec<_> = runner::jobs()
+fn main() -> ExitCode {
+    let _guard = aoc_common::trace::init();
+    let cli = Cli::parse();
+    let config = Config {
+        warmup: Duration::from_millis(cli.warmup_ms),
+        target: Duration::from_millis(cli.target_ms),
+        min_iterations: cli.min_iterations,
+        max_iterations: cli.max_iterations,
+    };
+
+    let timeout = Some(Duration::from_secs(cli.timeout));
+    isolate::silence_job_panics();
+
+    // Load the baseline up front so a bad path fails before a long run
+    let baseline = match &cli.baseline {
+        Some(path) => match bench::load(path) {
+            Ok(records) => Some(records),
+            Err(e) => {
+                eprintln!("error: {}: {}", path.display(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    println!(
+        "{:<14} {:>6} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "job", "iters", "min", "median", "mean", "stddev", "p95", "parse", "solve"
+    );
+    let records: Vec<Record> = runner::jobs_with_input()
         .iter()
-        .map(|j| {
-            (
-                j.1,
-                (0..RUNS)
-                    .map(|_| {
-                        let took = Timer::new();
-                        j.0();
-                        took.took().into_std()
-                    })
-                    .min()
-                    .unwrap(),
-            )
+        .filter(|(job, _)| cli.day.is_none_or(|day| job.day == day))
+        .filter_map(|(job, input)| {
+            let record = match bench::measure(job, input, &config, timeout) {
+                Ok(record) => record,
+                Err(e) => {
+                    eprintln!("skipping {}: {}", job, e);
+                    return None;
+                }
+            };
+            let stats = &record.stats;
+            let phase_median = |phase: &Option<bench::Stats>| {
+                phase
+                    .as_ref()
+                    .map(|p| Took::from_std(p.median()).to_string())
+            };
+            println!(
+                "{:<14} {:>6} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+                job.to_string(),
+                stats.iterations,
+                Took::from_std(stats.min()),
+                Took::from_std(stats.median()),
+                Took::from_std(stats.mean()),
+                Took::from_std(stats.stddev()),
+                Took::from_std(stats.p95()),
+                phase_median(&record.parse).unwrap_or_default(),
+                phase_median(&record.solve).unwrap_or_default(),
+            );
+            Some(record)
         })
         .collect();
 
-    times.iter().for_each(|t| Took::from_std(t.1).describe(t.0));
-    Took::from_std(times.into_iter().map(|(_, t)| t).sum()).describe("everything");
-}
\ No newline at end of file
+    let total: u64 = records.iter().map(|r| r.stats.median_ns).sum();
+    Took::from_std(Duration::from_nanos(total)).describe("everything (median)");
+
+    for path in std::iter::once(&cli.output).chain(&cli.save_baseline) {
+        if let Err(e) = bench::save(path, &records) {
+            eprintln!("error: {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        println!("wrote {}", path.display());
+    }
+
+    if let Some(baseline) = baseline {
+        compare(&cli, &baseline, &records);
+    }
+
+    let budgets = Budgets {
+        per_day: cli.budgets.clone(),
+    };
+    let over = budgets.over_budget(&records);
+    if over.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+    for o in over {
+        eprintln!(
+            "day-{:02} took {}, over its {} budget",
+            o.day,
+            Took::from_std(o.took),
+            Took::from_std(o.budget)
+        );
+    }
+    ExitCode::FAILURE
+}
+
+fn compare(cli: &Cli, baseline: &[Record], records: &[Record]) {
+    let comparisons = bench::compare(baseline, records);
+
+    println!();
+    println!(
+        "{:<14} {:>10} {:>10} {:>9}",
+        "job", "baseline", "current", "delta"
+    );
+    for c in &comparisons {
+        let verdict = match c.change(cli.threshold) {
+            Change::Improved => "faster",
+            Change::Unchanged => "~",
+            Change::Regressed => "SLOWER",
+        };
+        println!(
+            "{:<14} {:>10} {:>10} {:>+8.1}% {}",
+            format!("day-{:02} part{}", c.day, c.part),
+            Took::from_std(Duration::from_nanos(c.baseline_ns)),
+            Took::from_std(Duration::from_nanos(c.current_ns)),
+            c.delta_pct(),
+            verdict,
+        );
+    }
+}
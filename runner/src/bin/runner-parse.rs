@@ -1,12 +1,41 @@
 use rayon::prelude::*;
-use took::Timer;
+use runner::isolate;
+use took::{Timer, Took};
 
 fn main() {
+    let _guard = aoc_common::trace::init();
     // Build threadpool with larger stack size
-    rayon::ThreadPoolBuilder::new().build_global().unwrap();
+    rayon::ThreadPoolBuilder::new()
+        .stack_size(isolate::STACK_SIZE)
+        .build_global()
+        .unwrap();
 
-    let jobs = runner::jobs();
+    let jobs = runner::jobs_with_input();
     let timer = Timer::new();
-    (0..jobs.len()).into_par_iter().for_each(|i| jobs[i].0());
-    timer.took().describe("everything");
-}
\ No newline at end of file
+    let results: Vec<_> = jobs
+        .par_iter()
+        .map(|(job, input)| job.run_timed(input))
+        .collect();
+    let elapsed = timer.took();
+
+    println!("{:<14} {:>10} {:>10}  answer", "job", "parse", "solve");
+    for ((job, _), result) in jobs.iter().zip(results) {
+        match result {
+            Ok((answer, phases)) => println!(
+                "{:<14} {:>10} {:>10}  {}",
+                job.to_string(),
+                Took::from_std(phases.parse),
+                Took::from_std(phases.solve),
+                answer
+            ),
+            Err(e) => println!(
+                "{:<14} {:>10} {:>10}  error: {}",
+                job.to_string(),
+                "-",
+                "-",
+                e
+            ),
+        }
+    }
+    elapsed.describe("everything");
+}
@@ -1,7 +1,411 @@
-use took::Timer;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
 
-fn main() {
+use aoc_common::input;
+use clap::{Parser, Subcommand};
+use rayon::prelude::*;
+use runner::bench::Config;
+use runner::diff::{self, Comparison};
+use runner::isolate::{self, Limits, Outcome, Run};
+use runner::report;
+use runner::scaffold;
+use runner::scale::{self, Report};
+use runner::verify::{self, Verdict};
+use runner::watch;
+use took::{Timer, Took};
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Seconds each part may run before it's reported as timed out
+    #[arg(long, global = true, default_value_t = 60)]
+    timeout: u64,
+    /// When running everything, run each part in its own process so runaway days can be killed
+    #[arg(long)]
+    isolate: bool,
+    /// Data memory limit per part in MiB, counting its 64 MiB stack
+    #[arg(long, requires = "isolate")]
+    memory_mb: Option<u64>,
+    /// Run one part on stdin for a parent started with --isolate
+    #[arg(long, hide = true)]
+    child: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, optionally just one part
+    Run {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<u8>,
+        /// Input file, or `-` for stdin. Defaults to the day's own input file
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// Check every answer against the day's stored `outputN.txt`
+    Verify,
+    /// Run every variant of each part on the same input and flag any disagreement
+    Diff {
+        #[arg(long)]
+        day: Option<u8>,
+        /// Input file, or `-` for stdin. Defaults to each day's own input file
+        #[arg(long)]
+        input: Option<String>,
+    },
+    /// Print a made-up input for a day, e.g. to save as a stress test
+    Generate {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        size: usize,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+    },
+    /// Time a day on generated inputs of growing size and report how its time grows
+    Scale {
+        #[arg(long)]
+        day: u8,
+        #[arg(long)]
+        part: Option<u8>,
+        /// Comma-separated sizes. Defaults to the day's own sweep
+        #[arg(long, value_delimiter = ',')]
+        sizes: Vec<usize>,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Runs per size; the fastest is kept
+        #[arg(long, default_value_t = 3)]
+        runs: usize,
+    },
+    /// Run everything and write a markdown table of stars, answer hashes and timings
+    Report {
+        /// Where to write it. Flamegraph links are relative to the workspace root
+        #[arg(long, default_value = "REPORT.md")]
+        output: PathBuf,
+        /// Milliseconds to benchmark each part for
+        #[arg(long, default_value_t = 500)]
+        target_ms: u64,
+    },
+    /// Re-run a day's examples and real input every time its source or inputs change
+    Watch {
+        #[arg(long)]
+        day: u8,
+    },
+    /// Create `day-XX` from `daily-template` and register it with the runner
+    New {
+        #[arg(long)]
+        day: u8,
+    },
+}
+
+fn main() -> ExitCode {
+    let _guard = aoc_common::trace::init();
+    let cli = Cli::parse();
+    let timeout = Some(Duration::from_secs(cli.timeout));
+    isolate::silence_job_panics();
+    if let Some(key) = &cli.child {
+        let Some(job) = isolate::find(key) else {
+            eprintln!("error: no job {}", key);
+            return ExitCode::FAILURE;
+        };
+        isolate::child(job);
+        return ExitCode::SUCCESS;
+    }
+    match cli.command {
+        None => {
+            let limits = Limits {
+                timeout,
+                memory: cli.memory_mb.map(|mb| mb * 1024 * 1024),
+            };
+            run_all(cli.isolate, &limits)
+        }
+        Some(Command::Run { day, part, input }) => run_day(day, part, input.as_deref(), timeout),
+        Some(Command::Verify) => verify_all(timeout),
+        Some(Command::Diff { day, input }) => diff_variants(day, input.as_deref(), timeout),
+        Some(Command::Generate { day, size, seed }) => generate(day, size, seed),
+        Some(Command::Scale {
+            day,
+            part,
+            sizes,
+            seed,
+            runs,
+        }) => scale_day(day, part, &sizes, seed, runs, timeout),
+        Some(Command::Report { output, target_ms }) => write_report(&output, target_ms, timeout),
+        Some(Command::Watch { day }) => watch_day(day),
+        Some(Command::New { day }) => new_day(day),
+    }
+}
+
+fn run_all(subprocess: bool, limits: &Limits) -> ExitCode {
+    let exe = env::current_exe().expect("can't find the runner executable");
+    let jobs = runner::jobs_with_input();
     let timer = Timer::new();
-    runner::jobs().iter().for_each(|j| j.0());
-    timer.took().describe("everything");
-}
\ No newline at end of file
+    // Jobs run on their own threads with a larger stack, so the pool threads only wait on them
+    let runs: Vec<Run> = jobs
+        .into_par_iter()
+        .map(|(job, input)| {
+            if subprocess {
+                isolate::in_subprocess(&exe, job, input, limits)
+            } else {
+                isolate::in_thread(job, input, limits.timeout)
+            }
+        })
+        .collect();
+    let elapsed = timer.took();
+
+    println!(
+        "{:<14} {:<7} {:>10} {:>10} {:>10}  answer",
+        "job", "status", "wall", "parse", "solve"
+    );
+    for run in &runs {
+        let (parse, solve) = match run.outcome.phases() {
+            Some(phases) => (
+                Took::from_std(phases.parse).to_string(),
+                Took::from_std(phases.solve).to_string(),
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:<14} {:<7} {:>10} {:>10} {:>10}  {}",
+            run.job.to_string(),
+            run.outcome.status(),
+            Took::from_std(run.wall).to_string(),
+            parse,
+            solve,
+            run.outcome
+        );
+    }
+    elapsed.describe("everything");
+
+    let failed = runs.iter().filter(|run| !run.outcome.is_solved()).count();
+    if failed > 0 {
+        eprintln!("{} of {} jobs failed", failed, runs.len());
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn run_day(
+    day: u8,
+    part: Option<u8>,
+    input_arg: Option<&str>,
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let jobs = runner::find_jobs(day, part);
+    if jobs.is_empty() {
+        eprintln!("error: no solution registered for day {}", day);
+        return ExitCode::FAILURE;
+    }
+
+    // stdin can only be read once, so share it between parts
+    let stdin = match input_arg {
+        Some("-") => match input::read_stdin() {
+            Ok(input) => Some(input),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => None,
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for job in jobs {
+        let input = match &stdin {
+            Some(input) => Ok(input.clone()),
+            None => input::read(input_arg, &job.dir(), job.part),
+        };
+        match input.map(|input| isolate::in_thread(job, input, timeout).outcome) {
+            Ok(Outcome::Solved { answer, phases }) => {
+                println!("{}: {}", job, answer);
+                Took::from_std(phases.parse).describe("parse");
+                Took::from_std(phases.solve).describe("solve");
+                if let Some(alloc) = phases.alloc {
+                    println!("memory: {}", alloc);
+                }
+            }
+            Ok(outcome) => {
+                eprintln!("{}: {}", job, outcome);
+                code = ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("{}: {}", job, e);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+fn verify_all(timeout: Option<Duration>) -> ExitCode {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for job in runner::jobs() {
+        let verdict = match (verify::expected(job), job.input()) {
+            (Ok(None), _) => Verdict::Missing,
+            (Ok(expected), Ok(input)) => {
+                verify::judge(expected, isolate::in_thread(job, input, timeout).outcome)
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                println!("{}: missing ({})", job, e);
+                missing += 1;
+                continue;
+            }
+        };
+        println!("{}: {}", job, verdict);
+        match verdict {
+            Verdict::Pass => passed += 1,
+            Verdict::Fail { .. } | Verdict::Error(_) => failed += 1,
+            Verdict::Missing => missing += 1,
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn diff_variants(day: Option<u8>, input_arg: Option<&str>, timeout: Option<Duration>) -> ExitCode {
+    let groups: Vec<_> = diff::groups()
+        .into_iter()
+        .filter(|group| day.is_none_or(|day| group[0].day == day))
+        .collect();
+    if groups.is_empty() {
+        eprintln!("error: no part has more than one variant registered");
+        return ExitCode::FAILURE;
+    }
+
+    let stdin = match input_arg {
+        Some("-") => match input::read_stdin() {
+            Ok(input) => Some(input),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => None,
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for group in groups {
+        let job = group[0];
+        let input = match &stdin {
+            Some(input) => Ok(input.clone()),
+            None => input::read(input_arg, &job.dir(), job.part),
+        };
+        match input {
+            Ok(input) => {
+                let comparison = Comparison::run(&group, &input, timeout);
+                println!("{}", comparison);
+                if !comparison.agrees() {
+                    code = ExitCode::FAILURE;
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", job, e);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+fn generate(day: u8, size: usize, seed: u64) -> ExitCode {
+    match runner::generator(day) {
+        Some(generator) => {
+            println!("{}", (generator.generate)(size, seed));
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("error: no generator registered for day {}", day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn scale_day(
+    day: u8,
+    part: Option<u8>,
+    sizes: &[usize],
+    seed: u64,
+    runs: usize,
+    timeout: Option<Duration>,
+) -> ExitCode {
+    let (Some(generator), jobs) = (runner::generator(day), runner::find_jobs(day, part)) else {
+        eprintln!("error: no generator registered for day {}", day);
+        return ExitCode::FAILURE;
+    };
+    let sizes = if sizes.is_empty() {
+        generator.sizes
+    } else {
+        sizes
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    for job in jobs {
+        match scale::measure(job, generator, sizes, seed, runs, timeout) {
+            Ok(samples) => {
+                let report = Report {
+                    job,
+                    unit: generator.unit,
+                    samples,
+                };
+                println!("{}\n", report);
+            }
+            Err(e) => {
+                eprintln!("{}: {}", job, e);
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+fn write_report(output: &Path, target_ms: u64, timeout: Option<Duration>) -> ExitCode {
+    let config = Config {
+        warmup: Duration::from_millis(target_ms / 10),
+        target: Duration::from_millis(target_ms),
+        min_iterations: 3,
+        ..Config::default()
+    };
+    let report = report::collect(runner::workspace_root(), &config, timeout);
+    if let Err(e) = fs::write(output, report.to_string()) {
+        eprintln!("error: {}: {}", output.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!("wrote {} ({} stars)", output.display(), report.stars());
+    ExitCode::SUCCESS
+}
+
+fn watch_day(day: u8) -> ExitCode {
+    match watch::watch(day) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new_day(day: u8) -> ExitCode {
+    match scaffold::scaffold(day) {
+        Ok(written) => {
+            for path in written {
+                let path = path.strip_prefix(runner::workspace_root()).unwrap_or(&path);
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::time::Duration;
+
+use aoc_common::Answer;
+
+use crate::isolate::{self, Outcome};
+use crate::{jobs, variants, Job};
+
+/// What every implementation of one day/part answered for the same input.
+pub struct Comparison {
+    pub answers: Vec<(&'static Job, Outcome)>,
+}
+
+fn answer(outcome: &Outcome) -> Option<&Answer> {
+    match outcome {
+        Outcome::Solved { answer, .. } => Some(answer),
+        _ => None,
+    }
+}
+
+impl Comparison {
+    /// Runs each of `jobs` on `input` in isolation, giving each at most `timeout`.
+    pub fn run(jobs: &[&'static Job], input: &str, timeout: Option<Duration>) -> Comparison {
+        Comparison {
+            answers: jobs
+                .iter()
+                .map(|&job| {
+                    (
+                        job,
+                        isolate::in_thread(job, input.to_string(), timeout).outcome,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// True when every variant gave the same answer, or none of them gave one.
+    pub fn agrees(&self) -> bool {
+        let mut answers = self.answers.iter().map(|(_, outcome)| answer(outcome));
+        let first = answers.next().flatten();
+        answers.all(|answer| answer == first)
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((job, first)) = self.answers.first() else {
+            return Ok(());
+        };
+        let name = format!("day-{:02} part{}", job.day, job.part);
+        if self.agrees() {
+            return write!(
+                f,
+                "{}: {} variants agree on {}",
+                name,
+                self.answers.len(),
+                first
+            );
+        }
+
+        write!(f, "{}: variants DISAGREE", name)?;
+        for (job, outcome) in &self.answers {
+            write!(f, "\n  {}: {}", job.variant, outcome)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every day/part with more than one implementation, each with its main variant first.
+pub fn groups() -> Vec<Vec<&'static Job>> {
+    jobs()
+        .iter()
+        .map(|job| variants(job.day, job.part))
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Phases;
+
+    use super::*;
+
+    fn group(day: u8, part: u8) -> Vec<&'static Job> {
+        groups()
+            .into_iter()
+            .find(|group| group[0].day == day && group[0].part == part)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_groups() {
+        let day_07 = group(7, 1);
+        assert!(day_07[0].is_main());
+        assert_eq!(day_07[1].to_string(), "day-07 part1 [itertools]");
+        for group in groups() {
+            assert!(group[0].is_main());
+            let mut names: Vec<&str> = group.iter().map(|job| job.variant).collect();
+            names.sort_unstable();
+            names.dedup();
+            assert_eq!(
+                names.len(),
+                group.len(),
+                "{} has two variants named alike",
+                group[0]
+            );
+        }
+    }
+
+    fn solved(answer: u32) -> Outcome {
+        Outcome::Solved {
+            answer: Answer::from(answer),
+            phases: Phases::default(),
+        }
+    }
+
+    #[test]
+    fn test_agrees() {
+        let jobs = group(7, 1);
+        let example = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+        let comparison = Comparison::run(&jobs, example, None);
+        assert!(comparison.agrees());
+        assert_eq!(
+            comparison.to_string(),
+            "day-07 part1: 2 variants agree on 6440"
+        );
+
+        // Both reject it, so they still agree
+        assert!(Comparison::run(&jobs, "32T3K x", None).agrees());
+
+        let comparison = Comparison {
+            answers: vec![(jobs[0], solved(6440)), (jobs[1], solved(6441))],
+        };
+        assert!(!comparison.agrees());
+        assert_eq!(
+            comparison.to_string(),
+            "day-07 part1: variants DISAGREE\n  main: 6440\n  itertools: 6441"
+        );
+
+        let comparison = Comparison {
+            answers: vec![
+                (jobs[0], solved(6440)),
+                (jobs[1], Outcome::Panicked("oops".to_string())),
+            ],
+        };
+        assert!(!comparison.agrees());
+        assert!(comparison
+            .to_string()
+            .ends_with("itertools: panicked: oops"));
+    }
+
+    #[test]
+    fn test_variants_agree_on_puzzle_inputs() {
+        for group in groups() {
+            let Ok(input) = group[0].input() else {
+                continue;
+            };
+            let comparison = Comparison::run(&group, &input, None);
+            assert!(comparison.agrees(), "{}", comparison);
+        }
+    }
+}
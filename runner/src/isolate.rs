@@ -0,0 +1,391 @@
+//! Running jobs so that one bad day can't take the rest down with it.
+//!
+//! [`in_thread`] runs a job on its own thread, catching panics and giving up on it after a
+//! timeout. A thread can't be stopped from outside, though, so a job that never finishes keeps
+//! spinning until the process exits. [`in_subprocess`] runs it in a child process instead, which
+//! can be killed and given a memory limit.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::{input, Answer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{variants, Job, Phases};
+
+/// Stack for the thread a job runs on. Some days recurse deeply on the real inputs.
+pub const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// How often a parent checks whether its child has finished.
+const POLL: Duration = Duration::from_millis(5);
+
+/// How one job's run ended.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Outcome {
+    Solved {
+        #[serde(
+            serialize_with = "answer_to_text",
+            deserialize_with = "answer_from_text"
+        )]
+        answer: Answer,
+        phases: Phases,
+    },
+    /// The job rejected its input.
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+    /// The child process died without reporting back, e.g. after running out of memory.
+    Crashed(String),
+}
+
+// Answers cross to the parent as the text the day binaries would print
+fn answer_to_text<S: Serializer>(answer: &Answer, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(answer)
+}
+
+fn answer_from_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Answer, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    Ok(text.parse().unwrap())
+}
+
+impl Outcome {
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Outcome::Solved { .. })
+    }
+
+    /// A one-word summary for tables.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Outcome::Solved { .. } => "ok",
+            Outcome::Failed(_) => "error",
+            Outcome::Panicked(_) => "panic",
+            Outcome::TimedOut => "timeout",
+            Outcome::Crashed(_) => "crash",
+        }
+    }
+
+    pub fn phases(&self) -> Option<&Phases> {
+        match self {
+            Outcome::Solved { phases, .. } => Some(phases),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Solved { answer, .. } => write!(f, "{}", answer),
+            Outcome::Failed(e) => write!(f, "error: {}", e),
+            Outcome::Panicked(message) => write!(f, "panicked: {}", message),
+            Outcome::TimedOut => write!(f, "timed out"),
+            Outcome::Crashed(reason) => write!(f, "crashed: {}", reason),
+        }
+    }
+}
+
+/// One job's outcome and how long it took from start to finish.
+pub struct Run {
+    pub job: &'static Job,
+    pub wall: Duration,
+    pub outcome: Outcome,
+}
+
+/// What a child process may use before it's killed.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    /// Address space in bytes.
+    pub memory: Option<u64>,
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Keeps panics on the threads [`in_thread`] starts out of the output, since their message is
+/// reported in the job's [`Outcome`]. Panics anywhere else still print as usual.
+pub fn silence_job_panics() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let on_job_thread = thread::current()
+            .name()
+            .is_some_and(|name| name.starts_with("day-"));
+        if !on_job_thread {
+            default(info);
+        }
+    }));
+}
+
+/// Runs `job` on the current thread, turning a panic into an [`Outcome`].
+pub fn caught(job: &Job, input: &str) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| job.run_timed(input))) {
+        Ok(Ok((answer, phases))) => Outcome::Solved { answer, phases },
+        Ok(Err(e)) => Outcome::Failed(e.to_string()),
+        Err(payload) => Outcome::Panicked(panic_message(&*payload)),
+    }
+}
+
+/// Runs `job` on a fresh thread with a [`STACK_SIZE`] stack, waiting at most `timeout` for it.
+///
+/// A job that times out is left running in the background.
+pub fn in_thread(job: &'static Job, input: String, timeout: Option<Duration>) -> Run {
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(job.to_string())
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // The receiver is gone if we already gave up on this job
+            let _ = sender.send(caught(job, &input));
+        });
+    let outcome = match spawned {
+        Ok(_) => match timeout {
+            Some(timeout) => match receiver.recv_timeout(timeout) {
+                Ok(outcome) => outcome,
+                Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+                Err(RecvTimeoutError::Disconnected) => {
+                    Outcome::Crashed("job thread exited without an answer".to_string())
+                }
+            },
+            None => receiver.recv().unwrap_or_else(|_| {
+                Outcome::Crashed("job thread exited without an answer".to_string())
+            }),
+        },
+        Err(e) => Outcome::Crashed(format!("couldn't start a thread: {}", e)),
+    };
+    Run {
+        job,
+        wall: start.elapsed(),
+        outcome,
+    }
+}
+
+/// Names `job` on a child's command line, as `day/part/variant`.
+pub fn key(job: &Job) -> String {
+    format!("{}/{}/{}", job.day, job.part, job.variant)
+}
+
+/// The job named by [`key`].
+pub fn find(key: &str) -> Option<&'static Job> {
+    let mut fields = key.splitn(3, '/');
+    let day = fields.next()?.parse().ok()?;
+    let part = fields.next()?.parse().ok()?;
+    let variant = fields.next()?;
+    variants(day, part)
+        .into_iter()
+        .find(|job| job.variant == variant)
+}
+
+/// The child's side of [`in_subprocess`]: runs `job` on the input piped to it and prints the
+/// outcome.
+///
+/// Solutions print their own debugging to stdout, so the outcome goes on the last line.
+pub fn child(job: &'static Job) {
+    let outcome = match input::read_stdin() {
+        Ok(input) => in_thread(job, input, None).outcome,
+        Err(e) => Outcome::Failed(format!("can't read input: {}", e)),
+    };
+    println!("\n{}", serde_json::to_string(&outcome).unwrap());
+}
+
+fn spawn_reader(mut stream: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        let _ = stream.read_to_string(&mut text);
+        text
+    })
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // SAFETY: setrlimit is async-signal-safe and only touches the child
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_DATA, &limit) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+/// Waits for `child`, killing it once `timeout` has passed. Returns `None` if it was killed.
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Option<ExitStatus>> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL);
+    }
+}
+
+fn last_line(text: &str) -> Option<&str> {
+    text.lines().map(str::trim).rfind(|line| !line.is_empty())
+}
+
+/// Runs `job` on `input` in a child process of `exe`, which must hand `--child <key>` to
+/// [`child`].
+pub fn in_subprocess(exe: &Path, job: &'static Job, input: String, limits: &Limits) -> Run {
+    let start = Instant::now();
+    let outcome = match run_child(exe, job, input, limits) {
+        Ok(outcome) => outcome,
+        Err(e) => Outcome::Crashed(format!("couldn't run child: {}", e)),
+    };
+    Run {
+        job,
+        wall: start.elapsed(),
+        outcome,
+    }
+}
+
+fn run_child(exe: &Path, job: &'static Job, input: String, limits: &Limits) -> io::Result<Outcome> {
+    let mut command = Command::new(exe);
+    command
+        .args(["--child", &key(job)])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(bytes) = limits.memory {
+        limit_memory(&mut command, bytes);
+    }
+    let mut child = command.spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    // Written from a thread so a child that stops reading can't block us; if it dies first the
+    // write fails, and its exit status says why
+    thread::spawn(move || {
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout = spawn_reader(child.stdout.take().unwrap());
+    let stderr = spawn_reader(child.stderr.take().unwrap());
+
+    let Some(status) = wait(&mut child, limits.timeout)? else {
+        return Ok(Outcome::TimedOut);
+    };
+    let (stdout, stderr) = (stdout.join().unwrap(), stderr.join().unwrap());
+    if let Some(outcome) = last_line(&stdout).and_then(|line| serde_json::from_str(line).ok()) {
+        return Ok(outcome);
+    }
+    // Allocation failures abort with a message on stderr, which says more than the signal
+    let reason = stderr
+        .lines()
+        .find(|line| line.starts_with("memory allocation of"))
+        .or_else(|| last_line(&stderr));
+    Ok(Outcome::Crashed(match reason {
+        Some(line) => format!("{} ({})", line, status),
+        None => status.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{AocError, Solution};
+
+    use super::*;
+
+    struct Forever;
+
+    impl Solution for Forever {
+        const DAY: u8 = 8;
+        const PART: u8 = 1;
+        type Parsed<'a> = ();
+
+        fn parse(_input: &str) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn solve(_parsed: ()) -> Answer {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+
+    static FOREVER: Job = Job::new::<Forever>();
+
+    struct Broken;
+
+    impl Solution for Broken {
+        const DAY: u8 = 8;
+        const PART: u8 = 2;
+        type Parsed<'a> = ();
+
+        fn parse(_input: &str) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn solve(_parsed: ()) -> Answer {
+            panic!("the ghosts got lost")
+        }
+    }
+
+    static BROKEN: Job = Job::new::<Broken>();
+
+    #[test]
+    fn test_in_thread() {
+        let run = in_thread(&BROKEN, String::new(), Some(Duration::from_secs(10)));
+        assert_eq!(run.outcome.to_string(), "panicked: the ghosts got lost");
+
+        let job = find("13/1/main").unwrap();
+        let run = in_thread(job, "#.\n##".to_string(), None);
+        assert_eq!(run.outcome.status(), "error");
+
+        let run = in_thread(job, "#.\n.#\n##\n##".to_string(), None);
+        assert!(run.outcome.is_solved(), "{}", run.outcome);
+
+        let run = in_thread(&FOREVER, String::new(), Some(Duration::from_millis(50)));
+        assert_eq!(run.outcome.status(), "timeout");
+        assert!(run.wall >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_key() {
+        let job = &crate::VARIANTS[0];
+        assert_eq!(key(job), "7/1/itertools");
+        assert!(std::ptr::eq(find(&key(job)).unwrap(), job));
+        assert!(find("7/1").is_none());
+        assert!(find("7/3/main").is_none());
+    }
+
+    #[test]
+    fn test_outcome_round_trip() {
+        let outcome = Outcome::Solved {
+            answer: Answer::from(42u8),
+            phases: Phases::default(),
+        };
+        let json = serde_json::to_string(&outcome).unwrap();
+        let back: Outcome = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), "42");
+        assert_eq!(
+            last_line(&format!("debug output\n{}\n", json)),
+            Some(json.as_str())
+        );
+    }
+}
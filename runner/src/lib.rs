@@ -1,54 +1,231 @@
-pub fn jobs() -> &'static [(fn(), &'static str)] {
-    &[
-        (day01::part1, "day01a"),
-        // (day01b::main, "day01b"),
-        // (day02a::main, "day02a"),
-        // (day02b::main, "day02b"),
-        // (day03a::main, "day03a"),
-        // (day03b::main, "day03b"),
-        // (day04a::main, "day04a"),
-        // (day04b::main, "day04b"),
-        // (day05a::main, "day05a"),
-        // (day05b::main, "day05b"),
-        // (day06a::main, "day06a"),
-        // (day06b::main, "day06b"),
-        // (day07a::main, "day07a"),
-        // (day07b::main, "day07b"),
-        // (day08a::main, "day08a"),
-        // (day08b::main, "day08b"),
-        // (day09a::main, "day09a"),
-        // (day09b::main, "day09b"),
-        // (day10a::main, "day10a"),
-        // (day10b::main, "day10b"),
-        // (day11a::main, "day11a"),
-        // (day11b::main, "day11b"),
-        // (day12a::main, "day12a"),
-        // (day12b::main, "day12b"),
-        // (day13a::main, "day13a"),
-        // (day13b::main, "day13b"),
-        // (day14a::main, "day14a"),
-        // (day14b::main, "day14b"),
-        // (day15a::main, "day15a"),
-        // (day15b::main, "day15b"),
-        // (day16a::main, "day16a"),
-        // (day16b::main, "day16b"),
-        // (day17a::main, "day17a"),
-        // (day17b::main, "day17b"),
-        // (day18a::main, "day18a"),
-        // (day18b::main, "day18b"),
-        // (day19a::main, "day19a"),
-        // (day19b::main, "day19b"),
-        // (day20a::main, "day20a"),
-        // (day20b::main, "day20b"),
-        // (day21a::main, "day21a"),
-        // (day21b::main, "day21b"),
-        // (day22a::main, "day22a"),
-        // (day22b::main, "day22b"),
-        // (day23a::main, "day23a"),
-        // (day23b::main, "day23b"),
-        // (day24a::main, "day24a"),
-        // (day24b::main, "day24b"),
-        // (day25a::main, "day25a"),
-    ]
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::{input, solution, Answer, AocError, Solution};
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::{AllocStats, Counter};
+
+pub mod alloc;
+pub mod bench;
+pub mod diff;
+pub mod isolate;
+pub mod report;
+pub mod scaffold;
+pub mod scale;
+pub mod verify;
+pub mod watch;
+
+/// Time spent in each phase of a single run.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Phases {
+    pub parse: Duration,
+    pub solve: Duration,
+    /// What the run allocated, when the runner is built with the `alloc-stats` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+impl Phases {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// A single day/part pair the runner knows how to execute.
+#[derive(Clone, Copy)]
+pub struct Job {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'static str,
+    pub process: fn(&str) -> Result<Answer, AocError>,
+    pub process_timed: fn(&str) -> Result<(Answer, Phases), AocError>,
+}
+
+fn process_timed<S: Solution>(input: &str) -> Result<(Answer, Phases), AocError> {
+    let counter = Counter::start();
+    let start = Instant::now();
+    let input = input::normalize(input);
+    let parsed = S::parse(&input).map_err(|e| e.in_day(S::DAY))?;
+    let parsed_at = Instant::now();
+    let answer = S::solve(parsed);
+    let phases = Phases {
+        parse: parsed_at - start,
+        solve: parsed_at.elapsed(),
+        alloc: counter.finish(),
+    };
+    Ok((answer, phases))
+}
+
+impl Job {
+    pub const fn new<S: Solution>() -> Self {
+        Job {
+            day: S::DAY,
+            part: S::PART,
+            variant: S::VARIANT,
+            process: S::process,
+            process_timed: process_timed::<S>,
+        }
+    }
+
+    /// The `day-XX` crate directory holding this job's inputs.
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(format!("day-{:02}", self.day))
+    }
+
+    /// Reads this job's default input from its day directory.
+    pub fn input(&self) -> io::Result<String> {
+        input::read(None, &self.dir(), self.part)
+    }
+
+    /// Whether this is the implementation the runner uses by default.
+    pub fn is_main(&self) -> bool {
+        self.variant == solution::MAIN_VARIANT
+    }
+
+    pub fn run(&self, input: &str) -> Result<Answer, AocError> {
+        (self.process)(input)
+    }
+
+    /// Runs the job, timing its parse and solve phases separately.
+    pub fn run_timed(&self, input: &str) -> Result<(Answer, Phases), AocError> {
+        (self.process_timed)(input)
+    }
+}
+
+impl fmt::Display for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day-{:02} part{}", self.day, self.part)?;
+        if !self.is_main() {
+            write!(f, " [{}]", self.variant)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn workspace_root() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/.."))
+}
+
+/// Makes up puzzle inputs of any size for one day.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub day: u8,
+    /// Takes a size and a seed; the same pair always gives the same input.
+    pub generate: fn(usize, u64) -> String,
+    /// What the size counts, e.g. "grid side".
+    pub unit: &'static str,
+    /// Sizes to sweep when timing how the day scales.
+    pub sizes: &'static [usize],
+}
+
+/// Registers every day with the runner, each optionally followed by the alternative
+/// implementations of its parts, e.g. `day_07 => [part1_b::Part1]`.
+macro_rules! registry {
+    ($($day:ident $(=> [$($($variant:ident)::+),* $(,)?])?),* $(,)?) => {
+        static JOBS: &[Job] = &[
+            $(
+                Job::new::<$day::part1::Part1>(),
+                Job::new::<$day::part2::Part2>(),
+            )*
+        ];
+
+        /// Alternative implementations of registered parts, checked against them by [`diff`].
+        static VARIANTS: &[Job] = &[
+            $($($(Job::new::<$day$(::$variant)+>(),)*)?)*
+        ];
+
+        // A variant listed under the wrong day would never be compared with anything
+        $($($(
+            const _: () = assert!(
+                <$day$(::$variant)+ as Solution>::DAY == <$day::part1::Part1 as Solution>::DAY
+            );
+        )*)?)*
+
+        static GENERATORS: &[Generator] = &[
+            $(
+                Generator {
+                    day: <$day::part1::Part1 as Solution>::DAY,
+                    generate: $day::generate::generate,
+                    unit: $day::generate::SIZE_UNIT,
+                    sizes: $day::generate::SIZES,
+                },
+            )*
+        ];
+
+        static TITLES: &[(u8, &str)] = &[
+            $((<$day::part1::Part1 as Solution>::DAY, $day::TITLE),)*
+        ];
+    };
+}
+
+registry![
+    day_01, day_02, day_03, day_04, day_05, day_06, day_07 => [part1_b::Part1], day_08, day_09,
+    day_10, day_11, day_12, day_13, day_14, day_15, day_16,
+];
+
+/// Every registered job, ordered by day then part.
+pub fn jobs() -> &'static [Job] {
+    JOBS
+}
+
+/// Registered jobs for `day`, optionally narrowed to a single part.
+pub fn find_jobs(day: u8, part: Option<u8>) -> Vec<&'static Job> {
+    jobs()
+        .iter()
+        .filter(|job| job.day == day && part.is_none_or(|p| job.part == p))
+        .collect()
+}
+
+/// The input generator for `day`.
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
 }
 
+/// The puzzle title for `day`.
+pub fn title(day: u8) -> Option<&'static str> {
+    TITLES
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, title)| *title)
+}
+
+/// Every implementation of `day`'s `part`, the registered one first.
+pub fn variants(day: u8, part: u8) -> Vec<&'static Job> {
+    jobs()
+        .iter()
+        .chain(VARIANTS)
+        .filter(|job| job.day == day && job.part == part)
+        .collect()
+}
+
+/// Pairs every job with its input, skipping (and reporting) any whose input can't be read.
+pub fn jobs_with_input() -> Vec<(&'static Job, String)> {
+    jobs()
+        .iter()
+        .filter_map(|job| match job.input() {
+            Ok(input) => Some((job, input)),
+            Err(e) => {
+                eprintln!("skipping {}: {}", job, e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_survive_any_editor() {
+        for job in jobs() {
+            let generator = generator(job.day).unwrap();
+            let input = (generator.generate)(generator.sizes[0], 1);
+            let saved = format!("\u{feff}{}\r\n\r\n", input.replace('\n', "  \r\n"));
+            let answer = job.run(&input).unwrap();
+            assert_eq!(job.run(&saved), Ok(answer), "{}", job);
+        }
+    }
+}
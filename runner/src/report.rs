@@ -0,0 +1,319 @@
+//! A markdown summary of every day: which parts are solved, a fingerprint of each answer and how
+//! long it takes.
+//!
+//! Answers are shown as hashes rather than in full, since Advent of Code asks people not to
+//! publish them. The hash is still enough to spot an answer changing between reports.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use aoc_common::Answer;
+use took::Took;
+
+use crate::bench::{self, Config};
+use crate::isolate::{self, Outcome};
+use crate::verify::{self, Verdict};
+use crate::{jobs, title, Job};
+
+/// How far along one part is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the stored `outputN.txt`.
+    Solved,
+    /// There is an answer but no stored output to check it against.
+    Unverified,
+    /// The part still returns a placeholder.
+    Stub,
+    Failing(String),
+}
+
+/// Every 2023 answer is a whole number, so text is a placeholder left by a stub.
+pub fn is_stub(answer: &Answer) -> bool {
+    !answer.is_number()
+}
+
+pub fn classify(expected: Option<Answer>, outcome: Outcome) -> Status {
+    let answer = match outcome {
+        Outcome::Solved { answer, .. } => answer,
+        Outcome::Failed(e) => return Status::Failing(format!("error: {}", e)),
+        other => return Status::Failing(other.to_string()),
+    };
+    if is_stub(&answer) {
+        return Status::Stub;
+    }
+    match verify::check(expected, Ok(answer)) {
+        Verdict::Pass => Status::Solved,
+        Verdict::Missing => Status::Unverified,
+        _ => Status::Failing("wrong answer".to_string()),
+    }
+}
+
+/// A short, stable fingerprint of an answer (FNV-1a, truncated).
+pub fn hash(answer: &Answer) -> String {
+    let hash = answer
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)[..8].to_string()
+}
+
+/// One part's row in the report.
+#[derive(Debug, Clone)]
+pub struct Part {
+    pub part: u8,
+    pub status: Status,
+    pub hash: Option<String>,
+    pub median: Option<Duration>,
+}
+
+/// Runs `job` once to classify it, then benchmarks it if it produced an answer in time.
+pub fn run_part(job: &'static Job, config: &Config, timeout: Option<Duration>) -> Part {
+    let input = match job.input() {
+        Ok(input) => input,
+        Err(e) => {
+            return Part {
+                part: job.part,
+                status: Status::Failing(format!("can't read input: {}", e)),
+                hash: None,
+                median: None,
+            }
+        }
+    };
+    let outcome = isolate::in_thread(job, input.clone(), timeout).outcome;
+    let answer = match &outcome {
+        Outcome::Solved { answer, .. } => Some(answer.clone()),
+        _ => None,
+    };
+    let expected = verify::expected(job).unwrap_or(None);
+    let status = classify(expected, outcome);
+    let timed = matches!(status, Status::Solved | Status::Unverified);
+    Part {
+        part: job.part,
+        hash: answer.filter(|_| timed).map(|answer| hash(&answer)),
+        median: timed
+            .then(|| bench::measure(job, &input, config, timeout).ok())
+            .flatten()
+            .map(|record| record.stats.median()),
+        status,
+    }
+}
+
+/// One day's row in the report.
+#[derive(Debug, Clone)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    pub parts: Vec<Part>,
+    /// Paths of the day's flamegraphs, relative to the workspace root.
+    pub flamegraphs: Vec<String>,
+}
+
+/// The flamegraphs among `files` for `day`, named as `just flamegraph` writes them.
+pub fn flamegraphs_for(files: &[String], day: u8) -> Vec<String> {
+    let prefix = format!("day-{:02}--", day);
+    let mut matching: Vec<String> = files
+        .iter()
+        .filter(|name| name.starts_with(&prefix) && name.ends_with(".svg"))
+        .map(|name| format!("flamegraphs/{}", name))
+        .collect();
+    matching.sort();
+    matching
+}
+
+fn list_flamegraphs(root: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(root.join("flamegraphs")) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect()
+}
+
+/// Runs every registered day and collects the report, giving up on any part after `timeout`.
+pub fn collect(root: &Path, config: &Config, timeout: Option<Duration>) -> Report {
+    let files = list_flamegraphs(root);
+    let mut days: Vec<Day> = Vec::new();
+    for job in jobs() {
+        let part = run_part(job, config, timeout);
+        match days.last_mut() {
+            Some(day) if day.day == job.day => day.parts.push(part),
+            _ => days.push(Day {
+                day: job.day,
+                title: title(job.day).unwrap_or(""),
+                parts: vec![part],
+                flamegraphs: flamegraphs_for(&files, job.day),
+            }),
+        }
+    }
+    Report { days }
+}
+
+pub struct Report {
+    pub days: Vec<Day>,
+}
+
+impl Report {
+    /// Solved parts, one star each as on the site.
+    pub fn stars(&self) -> usize {
+        self.days
+            .iter()
+            .flat_map(|day| &day.parts)
+            .filter(|part| part.status == Status::Solved)
+            .count()
+    }
+}
+
+fn cell(part: &Part) -> String {
+    let status = match &part.status {
+        Status::Solved => "⭐ solved".to_string(),
+        Status::Unverified => "unverified".to_string(),
+        Status::Stub => "stub".to_string(),
+        Status::Failing(reason) => format!("❌ {}", reason.replace('|', "\\|")),
+    };
+    let mut cell = status;
+    if let Some(hash) = &part.hash {
+        cell.push_str(&format!(" `{}`", hash));
+    }
+    if let Some(median) = part.median {
+        cell.push_str(&format!(" {}", Took::from_std(median)));
+    }
+    cell
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Advent of Code 2023")?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{} of {} stars. Answers are shown as hashes; times are medians. Unverified parts \
+             have an answer but no `outputN.txt` to check it against.",
+            self.stars(),
+            self.days.iter().map(|day| day.parts.len()).sum::<usize>()
+        )?;
+        writeln!(f)?;
+        writeln!(f, "| Day | Puzzle | Part 1 | Part 2 | Flamegraphs |")?;
+        writeln!(f, "|---:|---|---|---|---|")?;
+        for day in &self.days {
+            let part = |n: u8| {
+                day.parts
+                    .iter()
+                    .find(|part| part.part == n)
+                    .map_or("-".to_string(), cell)
+            };
+            let flamegraphs: Vec<String> = day
+                .flamegraphs
+                .iter()
+                .map(|path| {
+                    let name = path.rsplit("--").next().unwrap().trim_end_matches(".svg");
+                    format!("[{}]({})", name, path)
+                })
+                .collect();
+            writeln!(
+                f,
+                "| {} | {} | {} | {} | {} |",
+                day.day,
+                day.title,
+                part(1),
+                part(2),
+                flamegraphs.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Phases;
+
+    fn solved(answer: &str) -> Outcome {
+        Outcome::Solved {
+            answer: answer.parse().unwrap(),
+            phases: Phases::default(),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let expected = || Some(Answer::from(42u8));
+        assert_eq!(classify(expected(), solved("42\n")), Status::Solved);
+        assert_eq!(classify(None, solved("42")), Status::Unverified);
+        assert_eq!(classify(None, solved("part 2")), Status::Stub);
+        assert_eq!(
+            classify(expected(), solved("41")),
+            Status::Failing("wrong answer".to_string())
+        );
+        assert_eq!(
+            classify(
+                expected(),
+                Outcome::Panicked("index out of bounds".to_string())
+            ),
+            Status::Failing("panicked: index out of bounds".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(&Answer::from(42u8)), hash(&Answer::from(42u64)));
+        assert_ne!(hash(&Answer::from(42u8)), hash(&Answer::from(43u8)));
+        assert_eq!(hash(&Answer::from("")), "cbf29ce4");
+    }
+
+    #[test]
+    fn test_flamegraphs_for() {
+        let files: Vec<String> = [
+            "day-01--part2.svg",
+            "day-01--part1.svg",
+            "day-10--part1.svg",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+        assert_eq!(
+            flamegraphs_for(&files, 1),
+            vec![
+                "flamegraphs/day-01--part1.svg",
+                "flamegraphs/day-01--part2.svg"
+            ]
+        );
+        assert!(flamegraphs_for(&files, 2).is_empty());
+    }
+
+    #[test]
+    fn test_markdown() {
+        let report = Report {
+            days: vec![Day {
+                day: 10,
+                title: "Pipe Maze",
+                parts: vec![
+                    Part {
+                        part: 1,
+                        status: Status::Solved,
+                        hash: Some("0123abcd".to_string()),
+                        median: None,
+                    },
+                    Part {
+                        part: 2,
+                        status: Status::Stub,
+                        hash: None,
+                        median: None,
+                    },
+                ],
+                flamegraphs: vec!["flamegraphs/day-10--part1.svg".to_string()],
+            }],
+        };
+        assert_eq!(report.stars(), 1);
+        let markdown = report.to_string();
+        assert!(markdown.contains("1 of 2 stars"));
+        assert!(markdown.ends_with(
+            "| 10 | Pipe Maze | ⭐ solved `0123abcd` | stub | \
+             [part1](flamegraphs/day-10--part1.svg) |\n"
+        ));
+    }
+}
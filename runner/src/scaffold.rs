@@ -0,0 +1,242 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::workspace_root;
+
+/// Rows of `registry![...]` are wrapped to this width, as rustfmt would.
+const MAX_WIDTH: usize = 100;
+
+/// Fills in the `daily-template` placeholders for `day`.
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{project-name}}", &format!("day-{:02}", day))
+        .replace("{{crate_name}}", &format!("day_{:02}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Adds `day` to the runner's dependencies, keeping the days in order.
+///
+/// Returns `None` if it is already there.
+pub fn add_dependency(manifest: &str, day: u8) -> Option<String> {
+    let name = format!("day-{:02}", day);
+    let mut lines: Vec<String> = manifest.lines().map(str::to_string).collect();
+    let days: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("day-"))
+        .collect();
+    if days
+        .iter()
+        .any(|&i| lines[i].starts_with(&format!("{} ", name)))
+    {
+        return None;
+    }
+
+    // Day lines are zero-padded, so comparing them as text keeps them in numeric order
+    let at = days
+        .iter()
+        .find(|&&i| lines[i] > name)
+        .copied()
+        .or_else(|| days.last().map(|i| i + 1))
+        .unwrap_or(lines.len());
+    lines.insert(at, format!("{} = {{ path = \"../{}\" }}", name, name));
+    Some(lines.join("\n") + "\n")
+}
+
+/// Splits a `registry![...]` body into its entries, each a day and any variants listed after it.
+fn registry_entries(list: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+    let mut depth = 0;
+    for c in list.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(entry.split_whitespace().collect::<Vec<_>>().join(" "));
+                entry.clear();
+                continue;
+            }
+            _ => {}
+        }
+        entry.push(c);
+    }
+    entries.push(entry.split_whitespace().collect::<Vec<_>>().join(" "));
+    entries.retain(|entry| !entry.is_empty());
+    entries
+}
+
+/// Adds `day` to the `registry![...]` invocation in the runner's `lib.rs`.
+///
+/// Returns `None` if it is already registered or there's no registry to add it to.
+pub fn add_to_registry(source: &str, day: u8) -> Option<String> {
+    let start = source.find("registry![")? + "registry![".len();
+    // Variants are listed in brackets of their own, so skip over those to the end of the list
+    let mut depth = 0;
+    let end = start
+        + source[start..].find(|c| {
+            match c {
+                '[' => depth += 1,
+                ']' if depth == 0 => return true,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            false
+        })?;
+    let mut entries = registry_entries(&source[start..end]);
+    let name = format!("day_{:02}", day);
+    if entries
+        .iter()
+        .any(|entry| entry.split_whitespace().next() == Some(name.as_str()))
+    {
+        return None;
+    }
+    entries.push(name);
+    entries.sort();
+
+    let mut list = String::new();
+    let mut line = String::from("   ");
+    for entry in entries {
+        if line.len() + 1 + entry.len() + 1 > MAX_WIDTH {
+            list.push_str(&line);
+            list.push('\n');
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(&entry);
+        line.push(',');
+    }
+    list.push_str(&line);
+
+    Some(format!(
+        "{}\n{}\n{}",
+        &source[..start],
+        list,
+        &source[end..]
+    ))
+}
+
+fn template_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            template_files(&path, files)?;
+        } else if path.file_name().is_some_and(|name| name != "Cargo.lock") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Creates `day-XX` from `daily-template` and registers it with the runner.
+///
+/// Returns the files it wrote or changed. Refuses to touch a day that already exists.
+pub fn scaffold(day: u8) -> io::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there is no day {} in Advent of Code", day),
+        ));
+    }
+    let root = workspace_root();
+    let template = root.join("daily-template");
+    let dir = root.join(format!("day-{:02}", day));
+    if dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        ));
+    }
+
+    let mut files = Vec::new();
+    template_files(&template, &mut files)?;
+    let mut written = Vec::new();
+    for file in files {
+        let target = dir.join(file.strip_prefix(&template).unwrap());
+        fs::create_dir_all(target.parent().unwrap())?;
+        fs::write(&target, render(&fs::read_to_string(&file)?, day))?;
+        written.push(target);
+    }
+
+    let manifest = root.join("runner/Cargo.toml");
+    if let Some(updated) = add_dependency(&fs::read_to_string(&manifest)?, day) {
+        fs::write(&manifest, updated)?;
+        written.push(manifest);
+    }
+    let lib = root.join("runner/src/lib.rs");
+    if let Some(updated) = add_to_registry(&fs::read_to_string(&lib)?, day) {
+        fs::write(&lib, updated)?;
+        written.push(lib);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template =
+            "name = \"{{project-name}}\"\nuse {{crate_name}}::part1;\nconst DAY: u8 = {{day}};";
+        assert_eq!(
+            render(template, 7),
+            "name = \"day-07\"\nuse day_07::part1;\nconst DAY: u8 = 7;"
+        );
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest = "[dependencies]\nclap = \"4\"\n\n# Day solutions\n\
+                        day-01 = { path = \"../day-01\" }\nday-03 = { path = \"../day-03\" }\n";
+        let updated = add_dependency(manifest, 2).unwrap();
+        assert!(updated.contains(
+            "day-01 = { path = \"../day-01\" }\n\
+             day-02 = { path = \"../day-02\" }\n\
+             day-03 = { path = \"../day-03\" }\n"
+        ));
+        assert!(add_dependency(&updated, 2).is_none());
+
+        let updated = add_dependency(manifest, 17).unwrap();
+        assert!(updated
+            .ends_with("day-03 = { path = \"../day-03\" }\nday-17 = { path = \"../day-17\" }\n"));
+    }
+
+    #[test]
+    fn test_add_to_registry() {
+        let source = "registry![\n    \
+                      day_01, day_02, day_03, day_04, day_05, day_06, day_07 => [part1_b::Part1], day_08, day_09,\n    \
+                      day_10, day_11, day_12, day_13, day_14, day_15, day_16,\n\
+                      ];\n"
+            .to_string();
+        assert!(add_to_registry(&source, 1).is_none());
+        assert!(add_to_registry(&source, 7).is_none());
+
+        let updated = add_to_registry(&source, 25).unwrap();
+        assert!(updated.contains("day_25,\n];"));
+        let start = updated.find("registry![").unwrap();
+        for line in updated[start..]
+            .lines()
+            .take_while(|line| !line.starts_with(']'))
+        {
+            assert!(line.len() <= MAX_WIDTH, "{}", line);
+        }
+
+        // Re-wrapping an unchanged list must give back the file as rustfmt left it
+        let without = source.replace(" day_01,", "");
+        assert_eq!(add_to_registry(&without, 1), Some(source.clone()));
+    }
+
+    #[test]
+    fn test_registry_entries() {
+        assert_eq!(
+            registry_entries(
+                "\n    day_06, day_07 => [part1_b::Part1, part2_b::Part2],\n    day_08,\n"
+            ),
+            vec![
+                "day_06",
+                "day_07 => [part1_b::Part1, part2_b::Part2]",
+                "day_08"
+            ]
+        );
+    }
+}
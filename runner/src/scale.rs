@@ -0,0 +1,182 @@
+use std::fmt;
+
+use std::time::Duration;
+
+use took::Took;
+
+use crate::isolate::{self, Outcome};
+use crate::{Generator, Job, Phases};
+
+/// One job's timing on one generated input.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub size: usize,
+    pub bytes: usize,
+    pub phases: Phases,
+}
+
+/// Times `job` on a generated input of each size, keeping the fastest of `runs` runs.
+///
+/// Each run gets `timeout` to finish; the error says which size failed and how.
+pub fn measure(
+    job: &'static Job,
+    generator: &Generator,
+    sizes: &[usize],
+    seed: u64,
+    runs: usize,
+    timeout: Option<Duration>,
+) -> Result<Vec<Sample>, String> {
+    sizes
+        .iter()
+        .map(|&size| {
+            let input = (generator.generate)(size, seed);
+            let mut best: Option<Phases> = None;
+            for _ in 0..runs.max(1) {
+                let phases = match isolate::in_thread(job, input.clone(), timeout).outcome {
+                    Outcome::Solved { phases, .. } => phases,
+                    outcome => return Err(format!("size {}: {}", size, outcome)),
+                };
+                if best.is_none_or(|best| phases.total() < best.total()) {
+                    best = Some(phases);
+                }
+            }
+            Ok(Sample {
+                size,
+                bytes: input.len(),
+                phases: best.unwrap(),
+            })
+        })
+        .collect()
+}
+
+/// The `k` in `time ~ size^k` between two samples.
+pub fn growth(from: &Sample, to: &Sample) -> Option<f64> {
+    let (before, after) = (
+        from.phases.total().as_secs_f64(),
+        to.phases.total().as_secs_f64(),
+    );
+    let ratio = to.size as f64 / from.size as f64;
+    (before > 0.0 && after > 0.0 && ratio.is_finite() && ratio > 0.0 && ratio != 1.0)
+        .then(|| (after / before).ln() / ratio.ln())
+}
+
+/// The `k` in `time ~ size^k` that best fits all the samples, by least squares on a log-log
+/// scale.
+pub fn fit(samples: &[Sample]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|sample| sample.size > 0 && !sample.phases.total().is_zero())
+        .map(|sample| {
+            let time = sample.phases.total().as_secs_f64();
+            ((sample.size as f64).ln(), time.ln())
+        })
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+    (points.len() >= 2 && variance > 0.0).then(|| covariance / variance)
+}
+
+/// How one job's time grows across a sweep of input sizes.
+pub struct Report<'a> {
+    pub job: &'a Job,
+    pub unit: &'static str,
+    pub samples: Vec<Sample>,
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} (size = {})", self.job, self.unit)?;
+        writeln!(
+            f,
+            "{:>10} {:>10} {:>12} {:>12} {:>8}",
+            "size", "bytes", "parse", "solve", "growth"
+        )?;
+        for (i, sample) in self.samples.iter().enumerate() {
+            let growth = match i.checked_sub(1).map(|prev| &self.samples[prev]) {
+                Some(prev) => {
+                    growth(prev, sample).map_or("-".to_string(), |k| format!("n^{:.2}", k))
+                }
+                None => "-".to_string(),
+            };
+            write!(
+                f,
+                "{:>10} {:>10} {:>12} {:>12} {:>8}",
+                sample.size,
+                sample.bytes,
+                Took::from_std(sample.phases.parse).to_string(),
+                Took::from_std(sample.phases.solve).to_string(),
+                growth
+            )?;
+            match sample.phases.alloc {
+                Some(alloc) => writeln!(f, "  {}", alloc)?,
+                None => writeln!(f)?,
+            }
+        }
+        match fit(&self.samples) {
+            Some(k) => write!(f, "overall: time grows like n^{:.2}", k),
+            None => write!(f, "overall: not enough samples to fit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{generator, jobs, variants};
+
+    fn sample(size: usize, micros: u64) -> Sample {
+        Sample {
+            size,
+            bytes: size,
+            phases: Phases {
+                parse: Duration::ZERO,
+                solve: Duration::from_micros(micros),
+                alloc: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_growth() {
+        let k = growth(&sample(100, 10), &sample(200, 40)).unwrap();
+        assert!((k - 2.0).abs() < 1e-9);
+        assert_eq!(growth(&sample(100, 10), &sample(100, 20)), None);
+        assert_eq!(growth(&sample(100, 0), &sample(200, 20)), None);
+    }
+
+    #[test]
+    fn test_fit() {
+        // Cubic with a constant factor
+        let samples: Vec<Sample> = [10, 20, 40, 80]
+            .iter()
+            .map(|&n| sample(n, 3 * (n * n * n) as u64))
+            .collect();
+        assert!((fit(&samples).unwrap() - 3.0).abs() < 1e-6);
+        assert_eq!(fit(&samples[..1]), None);
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for job in jobs() {
+            let generator = generator(job.day).unwrap();
+            let size = generator.sizes[0];
+            let input = (generator.generate)(size, 1);
+            assert_eq!(
+                input,
+                (generator.generate)(size, 1),
+                "{} is not seeded",
+                job
+            );
+            for variant in variants(job.day, job.part) {
+                if let Err(e) = variant.run(&input) {
+                    panic!("{} rejected a generated input: {}", variant, e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::fs;
+use std::io;
+
+use aoc_common::{Answer, AocError};
+
+use crate::isolate::Outcome;
+use crate::Job;
+
+/// Outcome of checking one job's answer against its stored output file.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// The job rejected its own input, panicked or ran out of time.
+    Error(String),
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Error(e) => write!(f, "ERROR ({})", e),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Reads the expected answer for `job` from `outputN.txt` in its day directory.
+///
+/// The stored files are captured program output, so any debug printing comes first and the
+/// answer is the last non-empty line. Returns `None` when there is no usable answer.
+pub fn expected(job: &Job) -> io::Result<Option<Answer>> {
+    let path = job.dir().join(format!("output{}.txt", job.part));
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(last_answer(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// The last non-empty line of some captured output, where day binaries print their answer.
+pub fn last_answer(contents: &str) -> Option<Answer> {
+    contents
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(|line| line.parse().unwrap())
+}
+
+pub fn check(expected: Option<Answer>, actual: Result<Answer, AocError>) -> Verdict {
+    let actual = match actual {
+        Ok(actual) => actual,
+        Err(e) => return Verdict::Error(e.to_string()),
+    };
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if expected == actual => Verdict::Pass,
+        Some(expected) => Verdict::Fail { expected, actual },
+    }
+}
+
+/// Like [`check`], for a run that may not have finished.
+pub fn judge(expected: Option<Answer>, outcome: Outcome) -> Verdict {
+    match outcome {
+        Outcome::Solved { answer, .. } => check(expected, Ok(answer)),
+        Outcome::Failed(e) => Verdict::Error(e),
+        other => Verdict::Error(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_last_answer() {
+        assert_eq!(last_answer("123\n"), Some(Answer::from(123u32)));
+        assert_eq!(
+            last_answer("Hand { bid: 1 }\nHand { bid: 2 }\n250087440\n\n"),
+            Some(Answer::from(250087440u64))
+        );
+        assert_eq!(last_answer("part 2\n"), Some(Answer::from("part 2")));
+        assert_eq!(last_answer(""), None);
+        assert_eq!(last_answer("\n  \n"), None);
+    }
+
+    #[test]
+    fn test_expected_after_debug_dump() {
+        // day 3's stored output starts with the schematic the old code printed
+        let job = crate::find_jobs(3, Some(1))[0];
+        assert_eq!(expected(job).unwrap(), Some(Answer::from(27837u32)));
+        assert_eq!(expected(crate::find_jobs(3, Some(2))[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let answer = |n: u64| Answer::from(n);
+        assert_eq!(
+            check(Some(answer(42)), Ok(Answer::from(42u8))),
+            Verdict::Pass
+        );
+        assert_eq!(check(None, Ok(answer(42))), Verdict::Missing);
+        assert_eq!(
+            check(Some(answer(41)), Ok(answer(42))),
+            Verdict::Fail {
+                expected: answer(41),
+                actual: answer(42)
+            }
+        );
+
+        let error = AocError::at("x", "x", "expected a number");
+        assert_eq!(
+            check(Some(answer(42)), Err(error.clone())),
+            Verdict::Error(error.to_string())
+        );
+        assert_eq!(
+            judge(Some(answer(42)), Outcome::TimedOut),
+            Verdict::Error("timed out".to_string())
+        );
+    }
+}
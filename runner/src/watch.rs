@@ -0,0 +1,281 @@
+//! `runner watch`: re-runs a day's examples and real input whenever one of its files changes.
+//!
+//! The runner can't reload a day it was compiled with, so everything here shells out to cargo.
+//! Files are polled rather than watched, which is plenty for one crate.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use aoc_common::Answer;
+
+use crate::verify::{self, last_answer};
+use crate::{find_jobs, workspace_root};
+
+/// How often to look for changes.
+const POLL: Duration = Duration::from_millis(300);
+
+/// When each file a day's answers depend on was last modified.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+fn visit(dir: &Path, recurse: bool, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    // Editors replace files as they save, so anything can vanish between listing and reading
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            if recurse {
+                visit(&path, true, files);
+            }
+        } else if recurse || path.extension().is_some_and(|ext| ext == "txt") {
+            if let Ok(modified) = metadata.modified() {
+                files.insert(path, modified);
+            }
+        }
+    }
+}
+
+impl Snapshot {
+    /// Everything under `dir/src`, plus the inputs, examples and outputs next to it.
+    pub fn take(dir: &Path) -> Snapshot {
+        let mut files = BTreeMap::new();
+        visit(&dir.join("src"), true, &mut files);
+        visit(dir, false, &mut files);
+        Snapshot(files)
+    }
+
+    /// Files added, removed or modified since `self`.
+    pub fn changed<'a>(&'a self, newer: &'a Snapshot) -> Vec<&'a Path> {
+        let removed = self.0.keys().filter(|path| !newer.0.contains_key(*path));
+        let touched = newer
+            .0
+            .iter()
+            .filter(|(path, modified)| self.0.get(*path) != Some(modified))
+            .map(|(path, _)| path);
+        removed.chain(touched).map(PathBuf::as_path).collect()
+    }
+}
+
+/// One part's answer next to the previous run's and the stored one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub part: u8,
+    /// `None` if the part failed to build or run.
+    pub answer: Option<Answer>,
+    pub previous: Option<Answer>,
+    pub expected: Option<Answer>,
+}
+
+impl Row {
+    pub fn verdict(&self) -> &'static str {
+        match (&self.answer, &self.expected) {
+            (None, _) => "error",
+            (Some(answer), Some(expected)) if answer == expected => "pass",
+            (Some(_), Some(_)) => "FAIL",
+            (Some(_), None) => "",
+        }
+    }
+}
+
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show =
+            |value: &Option<Answer>| value.as_ref().map_or("-".to_string(), Answer::to_string);
+        let previous = match &self.previous {
+            Some(previous) if self.previous == self.answer => format!("{} (same)", previous),
+            _ => show(&self.previous),
+        };
+        let line = format!(
+            "part{}: {:<18} previous {:<25} expected {:<18} {}",
+            self.part,
+            show(&self.answer),
+            previous,
+            show(&self.expected),
+            self.verdict()
+        );
+        write!(f, "{}", line.trim_end())
+    }
+}
+
+fn cargo() -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(workspace_root());
+    command
+}
+
+/// Runs the day's tests, letting cargo print as it goes. True if they all passed.
+pub fn run_examples(day: u8) -> io::Result<bool> {
+    let status = cargo()
+        .args(["test", "-q", "-p", &format!("day-{:02}", day)])
+        .status()?;
+    Ok(status.success())
+}
+
+/// Builds and runs one part's binary on its real input, returning the answer it printed.
+pub fn run_part(day: u8, part: u8) -> io::Result<Option<Answer>> {
+    let output = cargo()
+        .args(["run", "-q", "--release", "-p", &format!("day-{:02}", day)])
+        .args(["--bin", &format!("part{}", part)])
+        .stderr(Stdio::inherit())
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(output
+        .status
+        .success()
+        .then(|| last_answer(&stdout))
+        .flatten())
+}
+
+/// Waits for the day's files to change, then for them to stop changing.
+fn wait_for_change(dir: &Path, last: &Snapshot) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        thread::sleep(POLL);
+        let mut snapshot = Snapshot::take(dir);
+        let changed: Vec<PathBuf> = last
+            .changed(&snapshot)
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        // Saving several files, or an editor's write-then-rename, takes more than one poll
+        loop {
+            thread::sleep(POLL);
+            let settled = Snapshot::take(dir);
+            if settled == snapshot {
+                return (snapshot, changed);
+            }
+            snapshot = settled;
+        }
+    }
+}
+
+/// Re-runs `day`'s examples and then its real input every time its files change. Never returns
+/// unless cargo can't be started.
+pub fn watch(day: u8) -> io::Result<()> {
+    let jobs = find_jobs(day, None);
+    let Some(first) = jobs.first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no solution registered for day {}", day),
+        ));
+    };
+    let dir = first.dir();
+    let mut previous: BTreeMap<u8, Option<Answer>> = BTreeMap::new();
+    let mut snapshot = Snapshot::take(&dir);
+    println!("watching {}", dir.display());
+    loop {
+        if !run_examples(day)? {
+            println!("examples failed");
+        }
+        for job in &jobs {
+            let row = Row {
+                part: job.part,
+                answer: run_part(day, job.part)?,
+                previous: previous.get(&job.part).cloned().flatten(),
+                expected: verify::expected(job).unwrap_or(None),
+            };
+            println!("{}", row);
+            previous.insert(job.part, row.answer);
+        }
+
+        let (next, changed) = wait_for_change(&dir, &snapshot);
+        snapshot = next;
+        let names: Vec<String> = changed
+            .iter()
+            .map(|path| {
+                path.strip_prefix(&dir)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        println!("\n--- changed: {} ---", names.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(files: &[(&str, u64)]) -> Snapshot {
+        Snapshot(
+            files
+                .iter()
+                .map(|&(path, secs)| {
+                    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+                    (PathBuf::from(path), modified)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_changed() {
+        let before = snapshot(&[("src/part1.rs", 1), ("src/part2.rs", 1), ("input1.txt", 1)]);
+        let after = snapshot(&[("src/part1.rs", 2), ("src/part2.rs", 1), ("example.txt", 1)]);
+        assert_eq!(
+            before.changed(&after),
+            vec![
+                Path::new("input1.txt"),
+                Path::new("example.txt"),
+                Path::new("src/part1.rs")
+            ]
+        );
+        assert!(after.changed(&after).is_empty());
+    }
+
+    #[test]
+    fn test_take() {
+        let snapshot = Snapshot::take(&workspace_root().join("day-07"));
+        let names: Vec<String> = snapshot
+            .0
+            .keys()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert!(names.contains(&"part1_b.rs".to_string()));
+        assert!(names.contains(&"part1.rs".to_string()));
+        assert!(!names.contains(&"Cargo.toml".to_string()));
+    }
+
+    #[test]
+    fn test_row() {
+        let row = Row {
+            part: 1,
+            answer: Some(Answer::from(6440u32)),
+            previous: Some(Answer::from(6440u32)),
+            expected: Some(Answer::from(6440u32)),
+        };
+        assert_eq!(row.verdict(), "pass");
+        assert!(row.to_string().contains("previous 6440 (same)"));
+
+        let row = Row {
+            answer: Some(Answer::from(6441u32)),
+            previous: None,
+            ..row
+        };
+        assert_eq!(row.verdict(), "FAIL");
+        assert!(row.to_string().starts_with("part1: 6441 "));
+        assert_eq!(
+            Row {
+                answer: None,
+                ..row
+            }
+            .verdict(),
+            "error"
+        );
+    }
+}
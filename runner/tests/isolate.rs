@@ -0,0 +1,61 @@
+//! Runs jobs in child processes of the real runner binary, which is only built for integration
+//! tests.
+
+use std::path::Path;
+use std::time::Duration;
+
+use runner::isolate::{self, Limits};
+
+fn exe() -> &'static Path {
+    Path::new(env!("CARGO_BIN_EXE_runner"))
+}
+
+const NO_LIMITS: Limits = Limits {
+    timeout: None,
+    memory: None,
+};
+
+#[test]
+fn test_in_subprocess() {
+    let job = isolate::find("6/1/main").unwrap();
+    let input = "Time:      7  15   30\nDistance:  9  40  200".to_string();
+    let run = isolate::in_subprocess(exe(), job, input, &NO_LIMITS);
+    assert_eq!(run.outcome.to_string(), "288");
+
+    let run = isolate::in_subprocess(exe(), job, "Time: 7".to_string(), &NO_LIMITS);
+    assert_eq!(run.outcome.status(), "error");
+}
+
+#[test]
+fn test_subprocess_timeout() {
+    // Part 2 tries every edge of the grid, which takes seconds at this size
+    let job = isolate::find("16/2/main").unwrap();
+    let input = (runner::generator(16).unwrap().generate)(200, 1);
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(200)),
+        ..NO_LIMITS
+    };
+    let run = isolate::in_subprocess(exe(), job, input, &limits);
+    assert_eq!(run.outcome.status(), "timeout");
+    assert!(!run.outcome.is_solved());
+    assert!(run.wall < Duration::from_secs(5), "took {:?}", run.wall);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_subprocess_memory_limit() {
+    // Reading a 256 MiB input can't fit in 128 MiB
+    let job = isolate::find("6/1/main").unwrap();
+    let input = "Time: 7\n".repeat(32 * 1024 * 1024);
+    let limits = Limits {
+        memory: Some(128 * 1024 * 1024),
+        ..NO_LIMITS
+    };
+    let run = isolate::in_subprocess(exe(), job, input, &limits);
+    assert!(!run.outcome.is_solved());
+    assert!(
+        run.outcome.to_string().contains("out of memory"),
+        "{}",
+        run.outcome
+    );
+}